@@ -83,6 +83,24 @@ export async function run(module, src, outputBox) {
       }
     } break;
 
+    case 'evalLast':
+    case 'evalHead':
+    case 'evalTail': {
+      const input = exec.input;
+      const inputNext = exec.evalInputNext;
+      const steps = exec.evalSteps;
+      const truncated = exec.evalTruncated;
+      console.info({ input, steps, truncated });
+      const box = displayEvalInit(input, inputNext);
+      for (const { expr, reduced: reduced_range, next: next_range } of steps) {
+        displayEval(box, expr, reduced_range, next_range);
+      }
+      outputBox.scrollTo({
+        top: outputBox.scrollHeight,
+        behavior: 'smooth',
+      });
+    } break;
+
     case 'query': {
       const id = exec.input;
       const func = exec.queryResult;
@@ -1,7 +1,8 @@
-use crate::js_value::{JsCommand, JsContext, JsDisplayStyle, JsRunResult};
+use crate::js_value::{js_error, JsCommand, JsContext, JsDisplayStyle, JsRunResult};
 use crate::repository::{push_history_def, push_history_del};
 use tuber::Command;
 use tuber::Engine;
+use tuber::RunResult;
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
@@ -9,7 +10,7 @@ pub fn execute(
     context: JsContext,
     command: JsCommand,
     display_style: JsDisplayStyle,
-) -> Result<JsRunResult, JsError> {
+) -> Result<JsRunResult, JsValue> {
     if let Command::Del(id) = command.as_ref() {
         push_history_del(id).map_err(|err| JsError::new(&err.to_string()))?;
     }
@@ -19,8 +20,21 @@ pub fn execute(
     }
 
     let display_style = display_style.into();
-    let engine = Engine::new(context.into());
-    let result = engine.run(command.into());
+    // 最後まで簡約するコマンドでは、ω ω や Y f のように停止しない簡約を Diverged として打ち切る
+    let engine = match command.as_ref() {
+        Command::EvalLast(_) | Command::EvalTail(..) => {
+            Engine::new(context.into()).with_cycle_detection()
+        }
+        _ => Engine::new(context.into()),
+    };
+    let result = engine.run(command.into()).map_err(js_error)?;
+
+    // 変換結果を続けて評価できるように、変換結果が使う定義を Context に追加する
+    if let RunResult::Unlambda { definitions, .. } = &result {
+        for func in definitions {
+            push_history_def(func).map_err(|err| JsError::new(&err.to_string()))?;
+        }
+    }
 
     Ok((result, display_style).into())
 }
@@ -1,15 +1,19 @@
 mod command;
 mod context;
 mod display_style;
+mod error;
 mod evaluate;
 mod expression;
 mod function;
 mod run_result;
+mod strategy;
 
 pub use command::JsCommand;
 pub use context::JsContext;
 pub use display_style::JsDisplayStyle;
+pub use error::js_error;
 pub use evaluate::JsEval;
 pub use expression::JsExpr;
 pub use function::JsFunc;
 pub use run_result::JsRunResult;
+pub use strategy::JsStrategy;
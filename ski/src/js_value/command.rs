@@ -56,6 +56,11 @@ enum CommandJson {
     Query { identifier: String },
     Context,
     Unlambda { level: u8, expr: ExprJson },
+    UnlambdaCompare { expr: ExprJson },
+    UnlambdaContext { level: u8 },
+    Equivalent { lhs: ExprJson, rhs: ExprJson },
+    BitLength { expr: ExprJson },
+    Type { expr: ExprJson },
 }
 
 impl From<Command> for CommandJson {
@@ -89,6 +94,20 @@ impl From<Command> for CommandJson {
                 level,
                 expr: ExprJson::from(expr),
             },
+            Command::UnlambdaCompare(expr) => CommandJson::UnlambdaCompare {
+                expr: ExprJson::from(expr),
+            },
+            Command::UnlambdaContext(level) => CommandJson::UnlambdaContext { level },
+            Command::Equiv(lhs, rhs) => CommandJson::Equivalent {
+                lhs: ExprJson::from(lhs),
+                rhs: ExprJson::from(rhs),
+            },
+            Command::BitLength(expr) => CommandJson::BitLength {
+                expr: ExprJson::from(expr),
+            },
+            Command::Type(expr) => CommandJson::Type {
+                expr: ExprJson::from(expr),
+            },
         }
     }
 }
@@ -5,6 +5,7 @@ use wasm_bindgen::prelude::*;
 pub enum JsDisplayStyle {
     EcmaScript = "ECMAScript",
     LazyK = "Lazy_K",
+    Blc = "BLC",
 }
 
 impl From<JsDisplayStyle> for DisplayStyle {
@@ -12,6 +13,7 @@ impl From<JsDisplayStyle> for DisplayStyle {
         match js_display_style {
             JsDisplayStyle::EcmaScript => DisplayStyle::EcmaScript,
             JsDisplayStyle::LazyK => DisplayStyle::LazyK,
+            JsDisplayStyle::Blc => DisplayStyle::Blc,
             JsDisplayStyle::__Nonexhaustive => unreachable!(),
         }
     }
@@ -22,6 +24,7 @@ impl From<DisplayStyle> for JsDisplayStyle {
         match display_style {
             DisplayStyle::EcmaScript => JsDisplayStyle::EcmaScript,
             DisplayStyle::LazyK => JsDisplayStyle::LazyK,
+            DisplayStyle::Blc => JsDisplayStyle::Blc,
         }
     }
 }
@@ -31,6 +34,7 @@ impl AsRef<DisplayStyle> for JsDisplayStyle {
         match self {
             JsDisplayStyle::EcmaScript => &DisplayStyle::EcmaScript,
             JsDisplayStyle::LazyK => &DisplayStyle::LazyK,
+            JsDisplayStyle::Blc => &DisplayStyle::Blc,
             JsDisplayStyle::__Nonexhaustive => unreachable!(),
         }
     }
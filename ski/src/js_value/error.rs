@@ -0,0 +1,116 @@
+use super::expression::ExprJson;
+use serde::Serialize;
+use tuber::Error;
+use wasm_bindgen::prelude::*;
+
+/// tuber::Error を JS の Error オブジェクトに変換する
+///
+/// name にエラーの種類を、detail に種類ごとの詳細をセットする
+pub fn js_error(err: Error) -> JsValue {
+    let js_error = js_sys::Error::new(&err.to_string());
+    let detail = ErrorJson::from(err);
+    js_error.set_name(detail.name());
+    js_sys::Reflect::set(
+        &js_error,
+        &JsValue::from_str("detail"),
+        &serde_wasm_bindgen::to_value(&detail).unwrap(),
+    )
+    .unwrap();
+    js_error.into()
+}
+
+// ========================================================================== //
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum ErrorJson {
+    UndefinedFunction {
+        identifier: String,
+    },
+    ArityMismatch {
+        expected: usize,
+        actual: usize,
+    },
+    NotAFunction {
+        expr: ExprJson,
+    },
+    UnsupportedLevel {
+        level: u8,
+    },
+    StepLimitExceeded {
+        limit: usize,
+    },
+    #[serde(rename_all = "camelCase")]
+    Diverged {
+        cycle_start: usize,
+        period: usize,
+    },
+    NotEncodable {
+        expr: ExprJson,
+    },
+    NotANumeral {
+        expr: ExprJson,
+    },
+    OccursCheck {
+        var: String,
+        ty: String,
+    },
+    RecursiveDefinition {
+        identifier: String,
+    },
+}
+
+impl ErrorJson {
+    fn name(&self) -> &'static str {
+        match self {
+            ErrorJson::UndefinedFunction { .. } => "UndefinedFunctionError",
+            ErrorJson::ArityMismatch { .. } => "ArityMismatchError",
+            ErrorJson::NotAFunction { .. } => "NotAFunctionError",
+            ErrorJson::UnsupportedLevel { .. } => "UnsupportedLevelError",
+            ErrorJson::StepLimitExceeded { .. } => "StepLimitExceededError",
+            ErrorJson::Diverged { .. } => "DivergedError",
+            ErrorJson::NotEncodable { .. } => "NotEncodableError",
+            ErrorJson::NotANumeral { .. } => "NotANumeralError",
+            ErrorJson::OccursCheck { .. } => "OccursCheckError",
+            ErrorJson::RecursiveDefinition { .. } => "RecursiveDefinitionError",
+        }
+    }
+}
+
+impl From<Error> for ErrorJson {
+    fn from(err: Error) -> ErrorJson {
+        match err {
+            Error::UndefinedFunction(id) => ErrorJson::UndefinedFunction {
+                identifier: id.as_ref().to_string(),
+            },
+            Error::ArityMismatch { expected, actual } => {
+                ErrorJson::ArityMismatch { expected, actual }
+            }
+            Error::NotAFunction(expr) => ErrorJson::NotAFunction {
+                expr: ExprJson::from(expr),
+            },
+            Error::UnsupportedLevel(level) => ErrorJson::UnsupportedLevel { level },
+            Error::StepLimitExceeded(limit) => ErrorJson::StepLimitExceeded { limit },
+            Error::Diverged {
+                cycle_start,
+                period,
+            } => ErrorJson::Diverged {
+                cycle_start,
+                period,
+            },
+            Error::NotEncodable(expr) => ErrorJson::NotEncodable {
+                expr: ExprJson::from(expr),
+            },
+            Error::NotANumeral(expr) => ErrorJson::NotANumeral {
+                expr: ExprJson::from(expr),
+            },
+            Error::OccursCheck { var, ty } => ErrorJson::OccursCheck {
+                var: var.to_string(),
+                ty: ty.to_string(),
+            },
+            Error::RecursiveDefinition(id) => ErrorJson::RecursiveDefinition {
+                identifier: id.as_ref().to_string(),
+            },
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use super::{JsContext, JsDisplayStyle, JsExpr};
+use super::{js_error, JsContext, JsDisplayStyle, JsExpr, JsStrategy};
 use serde::{Deserialize, Serialize};
 use tuber::{
-    ecmascript_format, lazy_k_format, Context, DisplayStyle, Eval, EvalStep, Expr, Path, Tag,
+    blc_format, ecmascript_format, lazy_k_format, Context, DisplayStyle, Eval, EvalStep, Expr,
+    Path, StepKind, Strategy, Tag,
 };
 use wasm_bindgen::prelude::*;
 
@@ -11,25 +12,29 @@ pub struct JsEval(Eval);
 #[wasm_bindgen(js_class = Eval)]
 impl JsEval {
     #[wasm_bindgen(constructor)]
-    pub fn new(context: JsContext, expr: JsExpr) -> Self {
+    pub fn new(context: JsContext, expr: JsExpr, strategy: Option<JsStrategy>) -> Self {
         let context: Context = context.into();
         let expr: Expr = expr.into();
+        let strategy = strategy.map(Strategy::from).unwrap_or_default();
 
-        Self(Eval::new(context, expr))
+        Self(Eval::new(context, expr, strategy))
     }
 
-    pub fn next(&mut self, display_style: JsDisplayStyle) -> JsValue {
+    pub fn next(&mut self, display_style: JsDisplayStyle) -> Result<JsValue, JsValue> {
         match self.0.next() {
-            None => serde_wasm_bindgen::to_value(&JsNextResult {
+            None => Ok(serde_wasm_bindgen::to_value(&JsNextResult {
                 value: None,
                 done: true,
             })
-            .unwrap(),
-            Some(step) => serde_wasm_bindgen::to_value(&JsNextResult {
-                value: Some(JsEvalStep::from((step, display_style.into()))),
+            .unwrap()),
+            Some(step) => Ok(serde_wasm_bindgen::to_value(&JsNextResult {
+                value: Some(JsEvalStep::from((
+                    step.map_err(js_error)?,
+                    display_style.into(),
+                ))),
                 done: !self.has_next(),
             })
-            .unwrap(),
+            .unwrap()),
         }
     }
 
@@ -38,11 +43,12 @@ impl JsEval {
         self.0.clone().peekable().peek().is_some()
     }
 
-    pub fn chunk(&mut self, size: usize) -> Box<[JsValue]> {
+    pub fn chunk(&mut self, size: usize) -> Result<Box<[JsValue]>, JsValue> {
         let mut i = 0;
         let mut results = Vec::new();
         while i < size {
             if let Some(step) = self.0.next() {
+                let step = step.map_err(js_error)?;
                 let js_value = JsValue::from_str(&step.expr.to_string());
                 results.push(js_value);
             } else {
@@ -50,7 +56,7 @@ impl JsEval {
             }
             i = i + 1;
         }
-        results.into_boxed_slice()
+        Ok(results.into_boxed_slice())
     }
 }
 
@@ -77,6 +83,7 @@ pub struct JsNextResult {
 #[derive(Serialize, Deserialize)]
 pub struct JsEvalStep {
     pub step: usize,
+    pub kind: String,
     pub expr: String,
     pub reduced: Option<String>,
     pub next: Option<String>,
@@ -98,6 +105,7 @@ impl From<EvalStep> for JsEvalStep {
 
         JsEvalStep {
             step: step.step,
+            kind: step_kind(step.kind),
             expr: step.expr.to_string(),
             reduced: reduced_range,
             next: next_range,
@@ -115,6 +123,7 @@ impl From<(EvalStep, DisplayStyle)> for JsEvalStep {
         let formed = match display_style {
             DisplayStyle::EcmaScript => ecmascript_format(&step.expr, &paths),
             DisplayStyle::LazyK => lazy_k_format(&step.expr),
+            DisplayStyle::Blc => blc_format(&step.expr),
         };
 
         let reduced_range = reduced_path_to_range(&formed.mapping, &step.reduced_path);
@@ -124,6 +133,7 @@ impl From<(EvalStep, DisplayStyle)> for JsEvalStep {
 
         JsEvalStep {
             step: step.step,
+            kind: step_kind(step.kind),
             expr: formed.expr,
             reduced: reduced_range,
             next: next_range,
@@ -131,6 +141,13 @@ impl From<(EvalStep, DisplayStyle)> for JsEvalStep {
     }
 }
 
+fn step_kind(kind: StepKind) -> String {
+    match kind {
+        StepKind::Beta => String::from("beta"),
+        StepKind::Eta => String::from("eta"),
+    }
+}
+
 fn reduced_path_to_range(mapping: &[Tag], path: &Path) -> Option<String> {
     path.range(mapping)
         .map(|std::ops::Range { start, end }| format!("{},{}", start, end))
@@ -1,5 +1,10 @@
+use super::evaluate::JsEvalStep;
 use super::{JsContext, JsEval, JsExpr, JsFunc};
-use tuber::{ecmascript_format, lazy_k_format, DisplayStyle, Format, RunResult};
+use serde::Serialize;
+use tuber::{
+    blc_format, ecmascript_format, lazy_k_format, DisplayStyle, Equivalence, Format, Metrics,
+    Outcome, RunResult,
+};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen(js_name = RunResult)]
@@ -13,9 +18,17 @@ impl JsRunResult {
             RunResult::Del { .. } => String::from("del"),
             RunResult::Update { .. } => String::from("update"),
             RunResult::Eval { .. } => String::from("eval"),
+            RunResult::EvalLast { .. } => String::from("evalLast"),
+            RunResult::EvalHead { .. } => String::from("evalHead"),
+            RunResult::EvalTail { .. } => String::from("evalTail"),
             RunResult::Query { .. } => String::from("query"),
             RunResult::Context { .. } => String::from("context"),
             RunResult::Unlambda { .. } => String::from("unlambda"),
+            RunResult::UnlambdaCompare { .. } => String::from("unlambdaCompare"),
+            RunResult::UnlambdaContext { .. } => String::from("unlambdaContext"),
+            RunResult::Equiv { .. } => String::from("equiv"),
+            RunResult::BitLength { .. } => String::from("bitLength"),
+            RunResult::Type { .. } => String::from("type"),
         }
     }
 
@@ -26,9 +39,21 @@ impl JsRunResult {
             RunResult::Del { input, .. } => input.as_str().to_string(),
             RunResult::Update { input, .. } => input.format(&display_style),
             RunResult::Eval { input, .. } => input.format(&display_style),
+            RunResult::EvalLast { input, .. } => input.format(&display_style),
+            RunResult::EvalHead { input, .. } => input.format(&display_style),
+            RunResult::EvalTail { input, .. } => input.format(&display_style),
             RunResult::Query { input, .. } => input.as_str().to_string(),
             RunResult::Context { .. } => String::from("?"),
             RunResult::Unlambda { input, .. } => input.format(&display_style),
+            RunResult::UnlambdaCompare { input, .. } => input.format(&display_style),
+            RunResult::UnlambdaContext { .. } => String::from("?"),
+            RunResult::Equiv { lhs, rhs, .. } => format!(
+                "{} == {}",
+                lhs.format(&display_style),
+                rhs.format(&display_style)
+            ),
+            RunResult::BitLength { input, .. } => input.format(&display_style),
+            RunResult::Type { input, .. } => input.format(&display_style),
         }
     }
 
@@ -63,6 +88,7 @@ impl JsRunResult {
                 let formed = match display_style {
                     DisplayStyle::EcmaScript => ecmascript_format(&expr, &paths),
                     DisplayStyle::LazyK => lazy_k_format(&expr),
+                    DisplayStyle::Blc => blc_format(expr),
                 };
 
                 let arity: usize = path.get_arity();
@@ -112,6 +138,40 @@ impl JsRunResult {
         }
     }
 
+    #[wasm_bindgen(getter, js_name = evalSteps)]
+    pub fn eval_steps(&self) -> Option<Box<[JsValue]>> {
+        let steps = match &self.0 {
+            RunResult::EvalLast { result, .. } => result.iter().cloned().collect(),
+            RunResult::EvalHead { result, .. } => result.clone(),
+            RunResult::EvalTail { result, .. } => result.clone(),
+            _ => return None,
+        };
+
+        let display_style = self.1;
+        Some(
+            steps
+                .into_iter()
+                .map(|step| {
+                    serde_wasm_bindgen::to_value(&JsEvalStep::from((step, display_style))).unwrap()
+                })
+                .collect::<Vec<_>>()
+                .into_boxed_slice(),
+        )
+    }
+
+    #[wasm_bindgen(getter, js_name = evalTruncated)]
+    pub fn eval_truncated(&self) -> Option<bool> {
+        self.outcome().map(|outcome| outcome.truncated())
+    }
+
+    #[wasm_bindgen(getter, js_name = evalOutcome)]
+    pub fn eval_outcome(&self) -> JsValue {
+        match self.outcome() {
+            Some(outcome) => serde_wasm_bindgen::to_value(&OutcomeJson::from(*outcome)).unwrap(),
+            None => JsValue::UNDEFINED,
+        }
+    }
+
     #[wasm_bindgen(getter, js_name = queryResult)]
     pub fn query_result(&self) -> Option<JsFunc> {
         if let RunResult::Query { result, .. } = &self.0 {
@@ -141,12 +201,172 @@ impl JsRunResult {
 
     #[wasm_bindgen(getter, js_name = unlambdaLevel)]
     pub fn unlambda_level(&self) -> Option<u8> {
-        if let RunResult::Unlambda { level, .. } = &self.0 {
-            Some(*level)
+        match &self.0 {
+            RunResult::Unlambda { level, .. } => Some(*level),
+            RunResult::UnlambdaContext { level, .. } => Some(*level),
+            _ => None,
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = unlambdaContextResult)]
+    pub fn unlambda_context_result(&self) -> Option<JsContext> {
+        if let RunResult::UnlambdaContext { result, .. } = &self.0 {
+            Some(result.clone().into())
+        } else {
+            None
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = unlambdaMetrics)]
+    pub fn unlambda_metrics(&self) -> JsValue {
+        if let RunResult::Unlambda { metrics, .. } = &self.0 {
+            serde_wasm_bindgen::to_value(&MetricsJson::from(metrics)).unwrap()
+        } else {
+            JsValue::UNDEFINED
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = unlambdaCompareResult)]
+    pub fn unlambda_compare_result(&self) -> Option<Box<[JsValue]>> {
+        if let RunResult::UnlambdaCompare { result, .. } = &self.0 {
+            let display_style = self.1;
+            Some(
+                result
+                    .iter()
+                    .map(|(level, expr, metrics)| {
+                        let row = UnlambdaRowJson {
+                            level: *level,
+                            result: expr.format(&display_style),
+                            metrics: MetricsJson::from(metrics),
+                        };
+                        serde_wasm_bindgen::to_value(&row).unwrap()
+                    })
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = equivResult)]
+    pub fn equiv_result(&self) -> Option<String> {
+        if let RunResult::Equiv { result, .. } = &self.0 {
+            let result = match result {
+                Equivalence::Equal => "equal",
+                Equivalence::NotEqual => "notEqual",
+                Equivalence::Unknown => "unknown",
+            };
+            Some(String::from(result))
         } else {
             None
         }
     }
+
+    #[wasm_bindgen(getter, js_name = bitLengthResult)]
+    pub fn bit_length_result(&self) -> Option<usize> {
+        if let RunResult::BitLength { length, .. } = &self.0 {
+            Some(*length)
+        } else {
+            None
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = typeResult)]
+    pub fn type_result(&self) -> Option<String> {
+        if let RunResult::Type { result, .. } = &self.0 {
+            Some(result.format(&self.1))
+        } else {
+            None
+        }
+    }
+
+    #[wasm_bindgen(getter, js_name = bitLengthBits)]
+    pub fn bit_length_bits(&self) -> Option<String> {
+        if let RunResult::BitLength { bits, .. } = &self.0 {
+            Some(bits.clone())
+        } else {
+            None
+        }
+    }
+}
+
+impl JsRunResult {
+    fn outcome(&self) -> Option<&Outcome> {
+        match &self.0 {
+            RunResult::EvalLast { outcome, .. } => Some(outcome),
+            RunResult::EvalHead { outcome, .. } => Some(outcome),
+            RunResult::EvalTail { outcome, .. } => Some(outcome),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum OutcomeJson {
+    Normalized,
+    Truncated,
+    #[serde(rename_all = "camelCase")]
+    StepLimitExceeded {
+        likely_non_terminating: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Diverged {
+        cycle_start: usize,
+        period: usize,
+    },
+}
+
+impl From<Outcome> for OutcomeJson {
+    fn from(outcome: Outcome) -> OutcomeJson {
+        match outcome {
+            Outcome::Normalized => OutcomeJson::Normalized,
+            Outcome::Truncated => OutcomeJson::Truncated,
+            Outcome::StepLimitExceeded {
+                likely_non_terminating,
+            } => OutcomeJson::StepLimitExceeded {
+                likely_non_terminating,
+            },
+            Outcome::Diverged {
+                cycle_start,
+                period,
+            } => OutcomeJson::Diverged {
+                cycle_start,
+                period,
+            },
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct MetricsJson {
+    nodes: usize,
+    depth: usize,
+    combinators: Vec<(String, usize)>,
+    blc_length: Option<usize>,
+}
+
+impl From<&Metrics> for MetricsJson {
+    fn from(metrics: &Metrics) -> MetricsJson {
+        MetricsJson {
+            nodes: metrics.nodes,
+            depth: metrics.depth,
+            combinators: metrics
+                .combinators
+                .iter()
+                .map(|(id, count)| (id.as_str().to_string(), *count))
+                .collect(),
+            blc_length: metrics.blc_length,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct UnlambdaRowJson {
+    level: u8,
+    result: String,
+    metrics: MetricsJson,
 }
 
 impl From<RunResult> for JsRunResult {
@@ -0,0 +1,22 @@
+use tuber::Strategy;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+pub enum JsStrategy {
+    NormalOrder = "normalOrder",
+    ApplicativeOrder = "applicativeOrder",
+    CallByName = "callByName",
+    CallByValue = "callByValue",
+}
+
+impl From<JsStrategy> for Strategy {
+    fn from(js_strategy: JsStrategy) -> Strategy {
+        match js_strategy {
+            JsStrategy::NormalOrder => Strategy::NormalOrder,
+            JsStrategy::ApplicativeOrder => Strategy::ApplicativeOrder,
+            JsStrategy::CallByName => Strategy::CallByName,
+            JsStrategy::CallByValue => Strategy::CallByValue,
+            JsStrategy::__Nonexhaustive => unreachable!(),
+        }
+    }
+}
@@ -15,6 +15,7 @@ pub fn get_display_style() -> Result<DisplayStyle> {
         None => Ok(DisplayStyle::EcmaScript),
         Some("ECMAScript") => Ok(DisplayStyle::EcmaScript),
         Some("Lazy_K") => Ok(DisplayStyle::LazyK),
+        Some("BLC") => Ok(DisplayStyle::Blc),
         _ => Err(anyhow!("Invalid display style")),
     }
 }
@@ -0,0 +1,19 @@
+use crate::context::Context;
+use crate::error::to_js_error;
+use crate::expression::Expr;
+use wasm_bindgen::prelude::*;
+
+/// Context に定義済みの関数を展開して、式を Binary Lambda Calculus のビット列に変換する
+#[wasm_bindgen(js_name = encodeBlc)]
+pub fn encode_blc(context: Context, expr: Expr) -> Result<String, JsValue> {
+    tuber::blc_encode(&context.into(), expr.into()).map_err(to_js_error)
+}
+
+/// Binary Lambda Calculus のビット列を式に変換する
+#[wasm_bindgen(js_name = parseBlc)]
+pub fn parse_blc(input: &str) -> Result<Expr, JsError> {
+    match tuber::parse_blc(input) {
+        Ok(expr) => Ok(expr.into()),
+        Err(err) => Err(JsError::new(&err.to_string())),
+    }
+}
@@ -1,3 +1,4 @@
+use crate::error::to_js_parse_error;
 use crate::{expression::Expr, function::Func};
 use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
@@ -15,6 +16,11 @@ pub enum Command {
     Query { identifier: String },
     Context,
     Unlambda { level: u8, expr: Expr },
+    UnlambdaCompare { expr: Expr },
+    UnlambdaContext { level: u8 },
+    Equivalent { lhs: Expr, rhs: Expr },
+    BitLength { expr: Expr },
+    Type { expr: Expr },
 }
 
 impl From<tuber::Command> for Command {
@@ -48,6 +54,20 @@ impl From<tuber::Command> for Command {
                 level,
                 expr: Expr::from(expr),
             },
+            tuber::Command::UnlambdaCompare(expr) => Command::UnlambdaCompare {
+                expr: Expr::from(expr),
+            },
+            tuber::Command::UnlambdaContext(level) => Command::UnlambdaContext { level },
+            tuber::Command::Equiv(lhs, rhs) => Command::Equivalent {
+                lhs: Expr::from(lhs),
+                rhs: Expr::from(rhs),
+            },
+            tuber::Command::BitLength(expr) => Command::BitLength {
+                expr: Expr::from(expr),
+            },
+            tuber::Command::Type(expr) => Command::Type {
+                expr: Expr::from(expr),
+            },
         }
     }
 }
@@ -64,14 +84,23 @@ impl From<Command> for tuber::Command {
             Command::Query { identifier } => tuber::Command::Query(identifier.into()),
             Command::Context => tuber::Command::Context,
             Command::Unlambda { level, expr } => tuber::Command::Unlambda(level, expr.into()),
+            Command::UnlambdaCompare { expr } => tuber::Command::UnlambdaCompare(expr.into()),
+            Command::UnlambdaContext { level } => tuber::Command::UnlambdaContext(level),
+            Command::Equivalent { lhs, rhs } => tuber::Command::Equiv(lhs.into(), rhs.into()),
+            Command::BitLength { expr } => tuber::Command::BitLength(expr.into()),
+            Command::Type { expr } => tuber::Command::Type(expr.into()),
         }
     }
 }
 
+/// 構文解析に失敗したときは、name が "ParseError" で detail に位置と期待したトークンを持つ Error を投げる
 #[wasm_bindgen(js_name = parseCommand)]
-pub fn parse_command(input: &str) -> Result<Command, JsError> {
+pub fn parse_command(input: &str) -> Result<Command, JsValue> {
     match tuber::parse_command(input) {
         Ok(command) => Ok(command.into()),
-        Err(err) => Err(JsError::new(&err.to_string())),
+        Err(err) => match err.downcast::<tuber::ParseError>() {
+            Ok(parse_error) => Err(to_js_parse_error(parse_error)),
+            Err(err) => Err(JsError::new(&err.to_string()).into()),
+        },
     }
 }
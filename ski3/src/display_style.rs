@@ -7,6 +7,7 @@ use wasm_bindgen::prelude::*;
 pub enum DisplayStyle {
     EcmaScript,
     LazyK,
+    Blc,
 }
 
 impl From<DisplayStyle> for tuber::DisplayStyle {
@@ -14,6 +15,7 @@ impl From<DisplayStyle> for tuber::DisplayStyle {
         match ski_display_style {
             DisplayStyle::EcmaScript => tuber::DisplayStyle::EcmaScript,
             DisplayStyle::LazyK => tuber::DisplayStyle::LazyK,
+            DisplayStyle::Blc => tuber::DisplayStyle::Blc,
         }
     }
 }
@@ -23,6 +25,7 @@ impl From<tuber::DisplayStyle> for DisplayStyle {
         match tuber_display_style {
             tuber::DisplayStyle::EcmaScript => DisplayStyle::EcmaScript,
             tuber::DisplayStyle::LazyK => DisplayStyle::LazyK,
+            tuber::DisplayStyle::Blc => DisplayStyle::Blc,
         }
     }
 }
@@ -32,6 +35,7 @@ impl From<&tuber::DisplayStyle> for DisplayStyle {
         match tuber_display_style {
             tuber::DisplayStyle::EcmaScript => DisplayStyle::EcmaScript,
             tuber::DisplayStyle::LazyK => DisplayStyle::LazyK,
+            tuber::DisplayStyle::Blc => DisplayStyle::Blc,
         }
     }
 }
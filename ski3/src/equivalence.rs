@@ -0,0 +1,45 @@
+use crate::context::Context;
+use crate::error::to_js_error;
+use crate::expression::Expr;
+use serde::{Deserialize, Serialize};
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+#[derive(Tsify, Serialize, Deserialize)]
+#[tsify(into_wasm_abi, from_wasm_abi)]
+pub enum Equivalence {
+    Equal,
+    NotEqual,
+    Unknown,
+}
+
+impl From<tuber::Equivalence> for Equivalence {
+    fn from(tuber_equivalence: tuber::Equivalence) -> Equivalence {
+        match tuber_equivalence {
+            tuber::Equivalence::Equal => Equivalence::Equal,
+            tuber::Equivalence::NotEqual => Equivalence::NotEqual,
+            tuber::Equivalence::Unknown => Equivalence::Unknown,
+        }
+    }
+}
+
+/// 2つの式を正規形まで簡約し、α同値かどうかを判定する
+#[allow(non_snake_case)]
+#[wasm_bindgen(js_name = checkEquivalence)]
+pub fn check_equivalence(
+    context: Context,
+    lhs: Expr,
+    rhs: Expr,
+    stepLimit: Option<usize>,
+) -> Result<Equivalence, JsValue> {
+    let mut engine = tuber::Engine::new(context.into());
+    if let Some(step_limit) = stepLimit {
+        engine = engine.with_step_limit(step_limit);
+    }
+
+    match engine.run(tuber::Command::Equiv(lhs.into(), rhs.into())) {
+        Ok(tuber::RunResult::Equiv { result, .. }) => Ok(result.into()),
+        Ok(_) => unreachable!(),
+        Err(err) => Err(to_js_error(err)),
+    }
+}
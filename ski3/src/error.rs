@@ -0,0 +1,141 @@
+use crate::expression::Expr;
+use serde::Serialize;
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+/// tuber::Error の詳細
+///
+/// JS の Error オブジェクトの detail プロパティとして渡される
+/// ski の ErrorJson と同じく、種類を type に入れてフィールド名を camelCase にする
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+pub enum ErrorDetail {
+    UndefinedFunction { identifier: String },
+    ArityMismatch { expected: usize, actual: usize },
+    NotAFunction { expr: Expr },
+    UnsupportedLevel { level: u8 },
+    StepLimitExceeded { limit: usize },
+    Diverged { cycle_start: usize, period: usize },
+    NotEncodable { expr: Expr },
+    NotANumeral { expr: Expr },
+    OccursCheck { var: String, ty: String },
+    RecursiveDefinition { identifier: String },
+}
+
+impl ErrorDetail {
+    fn name(&self) -> &'static str {
+        match self {
+            ErrorDetail::UndefinedFunction { .. } => "UndefinedFunctionError",
+            ErrorDetail::ArityMismatch { .. } => "ArityMismatchError",
+            ErrorDetail::NotAFunction { .. } => "NotAFunctionError",
+            ErrorDetail::UnsupportedLevel { .. } => "UnsupportedLevelError",
+            ErrorDetail::StepLimitExceeded { .. } => "StepLimitExceededError",
+            ErrorDetail::Diverged { .. } => "DivergedError",
+            ErrorDetail::NotEncodable { .. } => "NotEncodableError",
+            ErrorDetail::NotANumeral { .. } => "NotANumeralError",
+            ErrorDetail::OccursCheck { .. } => "OccursCheckError",
+            ErrorDetail::RecursiveDefinition { .. } => "RecursiveDefinitionError",
+        }
+    }
+}
+
+impl From<tuber::Error> for ErrorDetail {
+    fn from(tuber_error: tuber::Error) -> ErrorDetail {
+        match tuber_error {
+            tuber::Error::UndefinedFunction(id) => ErrorDetail::UndefinedFunction {
+                identifier: id.as_ref().to_string(),
+            },
+            tuber::Error::ArityMismatch { expected, actual } => {
+                ErrorDetail::ArityMismatch { expected, actual }
+            }
+            tuber::Error::NotAFunction(expr) => ErrorDetail::NotAFunction {
+                expr: Expr::from(expr),
+            },
+            tuber::Error::UnsupportedLevel(level) => ErrorDetail::UnsupportedLevel { level },
+            tuber::Error::StepLimitExceeded(limit) => ErrorDetail::StepLimitExceeded { limit },
+            tuber::Error::Diverged {
+                cycle_start,
+                period,
+            } => ErrorDetail::Diverged {
+                cycle_start,
+                period,
+            },
+            tuber::Error::NotEncodable(expr) => ErrorDetail::NotEncodable {
+                expr: Expr::from(expr),
+            },
+            tuber::Error::NotANumeral(expr) => ErrorDetail::NotANumeral {
+                expr: Expr::from(expr),
+            },
+            tuber::Error::OccursCheck { var, ty } => ErrorDetail::OccursCheck {
+                var: var.to_string(),
+                ty: ty.to_string(),
+            },
+            tuber::Error::RecursiveDefinition(id) => ErrorDetail::RecursiveDefinition {
+                identifier: id.as_ref().to_string(),
+            },
+        }
+    }
+}
+
+/// tuber::Error を JS の Error オブジェクトに変換する
+///
+/// name にエラーの種類を、detail に ErrorDetail をセットする
+pub fn to_js_error(tuber_error: tuber::Error) -> JsValue {
+    let js_error = js_sys::Error::new(&tuber_error.to_string());
+    let detail = ErrorDetail::from(tuber_error);
+    js_error.set_name(detail.name());
+    js_sys::Reflect::set(
+        &js_error,
+        &JsValue::from_str("detail"),
+        &serde_wasm_bindgen::to_value(&detail).unwrap(),
+    )
+    .unwrap();
+    js_error.into()
+}
+
+/// tuber::ParseError の詳細
+///
+/// offset は入力の先頭からの文字数、line と col は 1 から数える
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct ParseErrorDetail {
+    offset: usize,
+    line: usize,
+    col: usize,
+    expected: Vec<String>,
+    style_tried: Vec<String>,
+}
+
+impl From<tuber::ParseError> for ParseErrorDetail {
+    fn from(parse_error: tuber::ParseError) -> ParseErrorDetail {
+        ParseErrorDetail {
+            offset: parse_error.offset,
+            line: parse_error.line,
+            col: parse_error.col,
+            expected: parse_error.expected,
+            style_tried: parse_error
+                .style_tried
+                .iter()
+                .map(|style| format!("{:?}", style))
+                .collect(),
+        }
+    }
+}
+
+/// tuber::ParseError を JS の Error オブジェクトに変換する
+///
+/// name に "ParseError" を、detail に ParseErrorDetail をセットする
+pub fn to_js_parse_error(parse_error: tuber::ParseError) -> JsValue {
+    let js_error = js_sys::Error::new(&parse_error.to_string());
+    let detail = ParseErrorDetail::from(parse_error);
+    js_error.set_name("ParseError");
+    js_sys::Reflect::set(
+        &js_error,
+        &JsValue::from_str("detail"),
+        &serde_wasm_bindgen::to_value(&detail).unwrap(),
+    )
+    .unwrap();
+    js_error.into()
+}
@@ -1,3 +1,4 @@
+use crate::context::Context;
 use crate::display_style::DisplayStyle;
 use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
@@ -67,6 +68,34 @@ pub fn render_expr(expr: Expr, displayStyle: DisplayStyle) -> String {
     tuber_expr.format(&tuber_display_style)
 }
 
+/// context に定義済みの関数と同じ部分式を、関数の名前に置き換えて整形する
+///
+/// FoldBack の構築は context の関数をすべて正規化するので、context ごとに一度だけ行い使い回す
+#[wasm_bindgen]
+pub struct Renderer {
+    fold_back: tuber::FoldBack,
+}
+
+#[wasm_bindgen]
+impl Renderer {
+    #[wasm_bindgen(constructor)]
+    pub fn new(context: Context) -> Self {
+        Self {
+            fold_back: tuber::FoldBack::new(&context.into()),
+        }
+    }
+
+    #[allow(non_snake_case)]
+    pub fn render(&self, expr: Expr, displayStyle: DisplayStyle) -> String {
+        let tuber_expr: tuber::Expr = expr.into();
+        let tuber_display_style: tuber::DisplayStyle = displayStyle.into();
+        let options = tuber::FormatOptions {
+            fold_back: Some(&self.fold_back),
+        };
+        tuber_expr.format_with(&tuber_display_style, &options)
+    }
+}
+
 // #[wasm_bindgen(js_name = formatExpr)]
 // pub fn format_expr(expr: Expr, display_style: DisplayStyle) -> String {
 //     let tuber_expr: tuber::Expr = expr.into();
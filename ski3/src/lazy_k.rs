@@ -0,0 +1,57 @@
+use crate::error::to_js_error;
+use serde::Serialize;
+use tsify_next::Tsify;
+use wasm_bindgen::prelude::*;
+
+/// step_limit を指定しなかったときの、簡約のステップ数の合計の上限
+///
+/// 出力が終わらないプログラムでも止まるよう、既定でも上限を設ける
+const STEP_LIMIT: usize = 1_000_000;
+
+/// Lazy K のプログラムの実行結果
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+#[serde(rename_all = "camelCase")]
+pub struct LazyKOutput {
+    /// 出力した文字列、上限に達した場合はそれまでに出力した分
+    output: String,
+
+    /// 簡約したステップ数の合計
+    step: usize,
+
+    /// ステップ数の上限に達して打ち切ったかどうか
+    step_limit_exceeded: bool,
+}
+
+/// Lazy K のプログラムに stdin を与えて実行し、出力を文字列として返す
+///
+/// 簡約のステップ数の合計が step_limit (省略した場合は STEP_LIMIT) を超えたところで打ち切り、
+/// それまでの出力を返す
+#[wasm_bindgen(js_name = runLazyK)]
+pub fn run_lazy_k(
+    program: &str,
+    stdin: &str,
+    step_limit: Option<usize>,
+) -> Result<LazyKOutput, JsValue> {
+    let program = tuber::parse_lazy_k_program(program)
+        .map_err(|err| JsValue::from(JsError::new(&err.to_string())))?;
+
+    let mut output = tuber::io::Output::new(tuber::Context::default(), program, stdin.as_bytes())
+        .with_step_limit(step_limit.unwrap_or(STEP_LIMIT));
+
+    let mut bytes = Vec::new();
+    let mut step_limit_exceeded = false;
+    for byte in output.by_ref() {
+        match byte {
+            Ok(byte) => bytes.push(byte),
+            Err(tuber::Error::StepLimitExceeded(_)) => step_limit_exceeded = true,
+            Err(err) => return Err(to_js_error(err)),
+        }
+    }
+
+    Ok(LazyKOutput {
+        output: String::from_utf8_lossy(&bytes).into_owned(),
+        step: output.step(),
+        step_limit_exceeded,
+    })
+}
@@ -1,15 +1,23 @@
+mod blc;
 mod command;
 mod context;
 mod display_style;
+mod equivalence;
+mod error;
 mod expression;
 mod function;
 mod identifier;
+mod lazy_k;
 mod reducer;
 mod utils;
 
+pub use blc::{encode_blc, parse_blc};
 pub use command::{parse_command, Command};
 pub use context::{default_context, Context};
 pub use display_style::DisplayStyle;
-pub use expression::{parse_expr, render_expr, Expr};
+pub use equivalence::{check_equivalence, Equivalence};
+pub use error::ErrorDetail;
+pub use expression::{parse_expr, render_expr, Expr, Renderer};
 pub use function::Func;
+pub use lazy_k::{run_lazy_k, LazyKOutput};
 pub use utils::set_panic_hook;
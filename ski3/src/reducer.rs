@@ -1,14 +1,15 @@
 use crate::context::Context;
 use crate::display_style::DisplayStyle;
+use crate::error::to_js_error;
 use crate::expression::Expr;
 use serde::{Deserialize, Serialize};
 use tsify_next::Tsify;
-use tuber::{self, ecmascript_format, lazy_k_format, Tag};
+use tuber::{self, blc_format, ecmascript_format, lazy_k_format, Tag};
 use wasm_bindgen::prelude::*;
 
 #[wasm_bindgen]
 pub struct Reducer {
-    reducer: tuber::Reducer,
+    reducer: tuber::History,
     reducible_path: Option<tuber::Path>,
     display_style: tuber::DisplayStyle,
 }
@@ -17,10 +18,24 @@ pub struct Reducer {
 impl Reducer {
     #[allow(non_snake_case)]
     #[wasm_bindgen(constructor)]
-    pub fn new(context: Context, expr: Expr, displayStyle: Option<DisplayStyle>) -> Self {
+    pub fn new(
+        context: Context,
+        expr: Expr,
+        displayStyle: Option<DisplayStyle>,
+        etaReduction: Option<bool>,
+        cycleDetection: Option<bool>,
+    ) -> Self {
         let tuber_context = context.into();
         let tuber_expr = expr.into();
-        let reducer = tuber::Reducer::new(tuber_context, tuber_expr);
+        let mut eval = tuber::Eval::new(tuber_context, tuber_expr, tuber::Strategy::default());
+        // 以前と同じ式が再び現れたら簡約を打ち切る、ステップごとに式を記録するので指定したときだけ行う
+        if cycleDetection.unwrap_or(false) {
+            eval = eval.with_cycle_detection();
+        }
+        if etaReduction.unwrap_or(false) {
+            eval = eval.with_eta_reduction();
+        }
+        let reducer = tuber::History::new(eval);
         let display_style = displayStyle
             .map(tuber::DisplayStyle::from)
             .unwrap_or(tuber::DisplayStyle::EcmaScript);
@@ -53,14 +68,38 @@ impl Reducer {
         self.reducible_path.is_some()
     }
 
+    #[wasm_bindgen(getter = hasPrev)]
+    pub fn has_prev(&self) -> bool {
+        self.reducer.step() > 0
+    }
+
+    /// 現在のステップ数
+    #[wasm_bindgen(getter)]
+    pub fn step(&self) -> usize {
+        self.reducer.step()
+    }
+
+    /// これまでに到達した最大のステップ数
+    #[wasm_bindgen(getter = knownSteps)]
+    pub fn known_steps(&self) -> usize {
+        self.reducer.known_steps()
+    }
+
+    /// 簡約が最後まで進んでいれば、簡約の総ステップ数
+    #[wasm_bindgen(getter = totalSteps)]
+    pub fn total_steps(&self) -> Option<usize> {
+        self.reducer.total_steps()
+    }
+
     #[wasm_bindgen(js_name = next)]
-    pub fn js_next(&mut self) -> Result<IteratorResult, JsError> {
-        let tuber_reduce_result = self.reducer.next();
+    pub fn js_next(&mut self) -> Result<IteratorResult, JsValue> {
+        let tuber_reduce_result = self.reducer.next().transpose().map_err(to_js_error)?;
         self.reducible_path = self.reducer.reducible_path();
 
         let ski_reduce_result = match tuber_reduce_result {
             Some(result) => Some(ReduceResult::new(
                 result.step,
+                result.kind,
                 result.expr.clone(),
                 result.reduced_path,
                 &self.reducible_path,
@@ -74,6 +113,46 @@ impl Reducer {
             value: ski_reduce_result,
         })
     }
+
+    /// 1 ステップ前に戻る
+    ///
+    /// 戻った先が 0 ステップ目の場合、value は undefined になる
+    #[wasm_bindgen(js_name = prev)]
+    pub fn js_prev(&mut self) -> Result<IteratorResult, JsValue> {
+        let done = !self.has_prev();
+        self.reducer.prev().map_err(to_js_error)?;
+        self.current_result(done)
+    }
+
+    /// 指定したステップに移動する
+    ///
+    /// 簡約が指定したステップより前に終わる場合は最後のステップに移動する
+    #[wasm_bindgen(js_name = seek)]
+    pub fn js_seek(&mut self, step: usize) -> Result<IteratorResult, JsValue> {
+        self.reducer.seek(step).map_err(to_js_error)?;
+        self.current_result(false)
+    }
+
+    fn current_result(&mut self, done: bool) -> Result<IteratorResult, JsValue> {
+        self.reducible_path = self.reducer.reducible_path();
+
+        let ski_reduce_result = match self.reducer.current() {
+            Some(result) => Some(ReduceResult::new(
+                result.step,
+                result.kind,
+                result.expr.clone(),
+                result.reduced_path.clone(),
+                &self.reducible_path,
+                &self.display_style,
+            )?),
+            None => None,
+        };
+
+        Ok(IteratorResult {
+            done,
+            value: ski_reduce_result,
+        })
+    }
 }
 
 #[derive(Tsify, Serialize)]
@@ -110,6 +189,7 @@ fn format_expr(
     let formed = match display_style {
         tuber::DisplayStyle::EcmaScript => ecmascript_format(expr, &paths),
         tuber::DisplayStyle::LazyK => lazy_k_format(expr),
+        tuber::DisplayStyle::Blc => blc_format(expr),
     };
 
     let reducible_range = match reducible_path {
@@ -133,13 +213,32 @@ fn format_expr(
 #[serde(rename_all = "camelCase")]
 struct ReduceResult {
     step: usize,
+    kind: StepKind,
     expr: Expr,
     formed: FormedReducedExpr,
 }
 
+/// 簡約の種類
+#[derive(Tsify, Serialize)]
+#[tsify(into_wasm_abi)]
+enum StepKind {
+    Beta,
+    Eta,
+}
+
+impl From<tuber::StepKind> for StepKind {
+    fn from(tuber_step_kind: tuber::StepKind) -> StepKind {
+        match tuber_step_kind {
+            tuber::StepKind::Beta => StepKind::Beta,
+            tuber::StepKind::Eta => StepKind::Eta,
+        }
+    }
+}
+
 impl ReduceResult {
     fn new(
         step: usize,
+        kind: tuber::StepKind,
         expr: tuber::Expr,
         reduced_path: tuber::Path,
         reducible_path: &Option<tuber::Path>,
@@ -148,6 +247,7 @@ impl ReduceResult {
         let formed = format_reduced_expr(&expr, &reduced_path, reducible_path, display_style)?;
         Ok(Self {
             step,
+            kind: kind.into(),
             expr: expr.into(),
             formed,
         })
@@ -197,6 +297,7 @@ fn format_reduced_expr(
     let formed = match display_style {
         tuber::DisplayStyle::EcmaScript => ecmascript_format(expr, &paths),
         tuber::DisplayStyle::LazyK => lazy_k_format(expr),
+        tuber::DisplayStyle::Blc => blc_format(expr),
     };
 
     let reduced_range = reduced_path_to_range(&formed.mapping, reduced_path)?;
@@ -0,0 +1,69 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use tuber::{parse_expr, Context, Eval, Expr, Func, GraphEval, Strategy};
+
+/// 簡約を打ち切るまでのステップ数
+const STEP_LIMIT: usize = 2000;
+
+fn setup() -> Context {
+    let mut context = Context::default();
+
+    // Y を使って再帰的に定義した階乗
+    context.def(Func::new(
+        "FACT".into(),
+        Vec::new(),
+        parse_expr("Y(f => n => IF(IS_ZERO(n), 1, MUL(n, f(PRED(n)))))").unwrap(),
+    ));
+
+    context
+}
+
+/// ベンチマークの対象とする式
+fn programs() -> Vec<(&'static str, Expr)> {
+    vec![
+        ("pow_2_4", parse_expr("POW(2, 4, :f, :x)").unwrap()),
+        ("mul_20_20", parse_expr("MUL(20, 20, :f, :x)").unwrap()),
+        ("fact_3", parse_expr("FACT(3, :f, :x)").unwrap()),
+        // Y の展開と引数の多いチャーチ数の簡約が続き、簡約基が深い位置に現れる
+        ("fact_4", parse_expr("FACT(4, :f, :x)").unwrap()),
+        ("pow_3_3", parse_expr("POW(3, 3, :f, :x)").unwrap()),
+        ("div_20_3", parse_expr("CAR(DIV(20, 3), :f, :x)").unwrap()),
+    ]
+}
+
+fn bench_eval(c: &mut Criterion) {
+    let context = setup();
+    let mut group = c.benchmark_group("eval");
+
+    for (name, expr) in programs() {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || Eval::new(context.clone(), expr.clone(), Strategy::NormalOrder),
+                |eval| eval.with_step_limit(STEP_LIMIT).count(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+/// GraphEval はスナップショットを作らずに正規形まで簡約する
+fn bench_graph_eval(c: &mut Criterion) {
+    let context = setup();
+    let mut group = c.benchmark_group("graph_eval");
+
+    for (name, expr) in programs() {
+        group.bench_function(name, |b| {
+            b.iter_batched(
+                || GraphEval::new(context.clone(), expr.clone()),
+                |eval| eval.with_step_limit(STEP_LIMIT).normalize(),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_eval, bench_graph_eval);
+criterion_main!(benches);
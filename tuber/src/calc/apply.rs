@@ -1,14 +1,14 @@
 use crate::context::Context;
+use crate::error::Error;
 use crate::expr::Expr;
-use anyhow::{anyhow, Result};
 
-pub fn apply(context: &Context, expr: &mut Expr, args: Vec<Expr>) -> Result<()> {
+pub fn apply(context: &Context, expr: &mut Expr, args: Vec<Expr>) -> Result<(), Error> {
     if let Some(arity) = arity(context, expr) {
         if arity != args.len() {
-            return Err(anyhow!(
-                "Number of arguments is mismatch: {} arg(s) expected",
-                arity
-            ));
+            return Err(Error::ArityMismatch {
+                expected: arity,
+                actual: args.len(),
+            });
         }
     }
 
@@ -24,10 +24,10 @@ pub fn apply(context: &Context, expr: &mut Expr, args: Vec<Expr>) -> Result<()>
                 *expr = func.apply(args);
                 Ok(())
             }
-            None => Err(anyhow!("Undefined function: {}", id)),
+            None => Err(Error::UndefinedFunction(id.to_owned())),
         },
 
-        _ => Err(anyhow!("Not a function: {}", expr)),
+        _ => Err(Error::NotAFunction(expr.to_owned())),
     }
 }
 
@@ -99,43 +99,67 @@ mod tests {
         let mut expr = expr::s("i");
         let args = vec![expr::s("a")];
         let result = apply(&context, &mut expr, args);
-        assert!(result.is_err()); // シンボルは関数に紐づくことがない
+        assert_eq!(result, Err(Error::NotAFunction(expr::s("i")))); // シンボルは関数に紐づくことがない
 
         // `y:a
         let mut expr = expr::v("y");
         let args = vec![expr::s("a")];
         let result = apply(&context, &mut expr, args);
-        assert!(result.is_err()); // context に存在しない不明な識別子に対して適用を試みた
+        assert_eq!(result, Err(Error::UndefinedFunction("y".into()))); // context に存在しない不明な識別子に対して適用を試みた
 
         // `(`i:a):b
         let mut expr = expr::a("i", ":a");
         let args = vec![expr::s("b")];
         let result = apply(&context, &mut expr, args);
-        assert!(result.is_err()); // apply() は ``(i):a:b 形式しか受け入れない
+        assert_eq!(result, Err(Error::NotAFunction(expr::a("i", ":a")))); // apply() は ``(i):a:b 形式しか受け入れない
 
         // `k:a
         let mut expr = expr::v("k");
         let args = vec![expr::s("a")];
         let result = apply(&context, &mut expr, args);
-        assert!(result.is_err()); // k の arity が 2 なのに対して引数の個数が少ない
+        assert_eq!(
+            result,
+            Err(Error::ArityMismatch {
+                expected: 2,
+                actual: 1
+            })
+        ); // k の arity が 2 なのに対して引数の個数が少ない
 
         // ```k:a:b:c
         let mut expr = expr::v("k");
         let args = vec![expr::s("a"), expr::s("b"), expr::s("c")];
         let result = apply(&context, &mut expr, args);
-        assert!(result.is_err()); // k の arity が 2 なのに対して引数の個数が多すぎる
+        assert_eq!(
+            result,
+            Err(Error::ArityMismatch {
+                expected: 2,
+                actual: 3
+            })
+        ); // k の arity が 2 なのに対して引数の個数が多すぎる
 
         // ^x.:a
         let mut expr = expr::l("x", ":a");
         let args = vec![];
         let result = apply(&context, &mut expr, args);
-        assert!(result.is_err()); // ラムダ抽象の arity が 1 なのに対して引数の個数が少ない
+        assert_eq!(
+            result,
+            Err(Error::ArityMismatch {
+                expected: 1,
+                actual: 0
+            })
+        ); // ラムダ抽象の arity が 1 なのに対して引数の個数が少ない
 
         // ``^x.:a:b:c
         let mut expr = expr::l("x", ":a");
         let args = vec![expr::s("b"), expr::s("c")];
         let result = apply(&context, &mut expr, args);
-        assert!(result.is_err()); // ラムダ抽象の arity が 1 なのに対して引数の個数が多すぎる
+        assert_eq!(
+            result,
+            Err(Error::ArityMismatch {
+                expected: 1,
+                actual: 2
+            })
+        ); // ラムダ抽象の arity が 1 なのに対して引数の個数が多すぎる
     }
 
     #[test]
@@ -0,0 +1,96 @@
+use super::expand::expand;
+use crate::context::Context;
+use crate::error::Error;
+use crate::expr::{self, DeBruijnExpr, Expr};
+use crate::to_string::{DisplayStyle, Format};
+
+/// 式を Binary Lambda Calculus のビット列に変換する
+///
+/// 式の中の自由変数は Context に定義済みの関数を展開して閉じた式にしてから変換する
+/// Context に定義されていない自由変数やシンボルが残る場合や、再帰的な定義を展開しようとした場合はエラーを返す
+pub fn blc_encode(context: &Context, expr: Expr) -> Result<String, Error> {
+    let expr = expand(context, expr)?;
+
+    if let Some(err) = unencodable(&DeBruijnExpr::from(&expr)) {
+        return Err(err);
+    }
+
+    Ok(expr.format(&DisplayStyle::Blc))
+}
+
+/// 左から順に探して最初に見つかった、BLC で表現できない部分式をエラーにして返す
+fn unencodable(expr: &DeBruijnExpr) -> Option<Error> {
+    match expr {
+        DeBruijnExpr::Bound { .. } => None,
+        DeBruijnExpr::Free(id) => Some(Error::UndefinedFunction(id.clone())),
+        DeBruijnExpr::Symbol(id) => Some(Error::NotEncodable(expr::s(id.clone()))),
+        DeBruijnExpr::Apply { lhs, rhs } => unencodable(lhs).or_else(|| unencodable(rhs)),
+        DeBruijnExpr::Lambda { body, .. } => unencodable(body),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::func;
+
+    fn setup() -> Context {
+        let i = func::new("i", vec!["x"], "x");
+        let k = func::new("k", vec!["x", "y"], "x");
+        let s = func::new(
+            "s",
+            vec!["x", "y", "z"],
+            expr::a(expr::a("x", "z"), expr::a("y", "z")),
+        );
+
+        Context::from(vec![i, k, s])
+    }
+
+    #[test]
+    fn test_blc_encode() {
+        let context = setup();
+
+        // λx.x
+        assert_eq!(
+            blc_encode(&context, expr::l("x", "x")),
+            Ok("0010".to_string())
+        );
+
+        // 定義済みの関数は展開する
+        assert_eq!(
+            blc_encode(&context, expr::v("k")),
+            Ok("0000110".to_string())
+        );
+        assert_eq!(
+            blc_encode(&context, expr::a("k", "i")),
+            Ok("0100001100010".to_string())
+        );
+        assert_eq!(
+            blc_encode(&context, expr::v("s")),
+            Ok("00000001011110100111010".to_string())
+        );
+
+        // 未定義の自由変数とシンボルは変換できない
+        assert_eq!(
+            blc_encode(&context, expr::a("k", "x")),
+            Err(Error::UndefinedFunction("x".into()))
+        );
+        assert_eq!(
+            blc_encode(&context, expr::a("k", ":a")),
+            Err(Error::NotEncodable(expr::s("a")))
+        );
+    }
+
+    #[test]
+    fn test_blc_encode_recursive() {
+        // 自分自身を参照する関数は展開が停止しないのでエラーにする
+        let mut context = setup();
+        context.def(func::new("LOOP", vec!["x"], expr::a("LOOP", "x")));
+        assert_eq!(
+            blc_encode(&context, expr::a("k", "LOOP")),
+            Err(Error::RecursiveDefinition("LOOP".into()))
+        );
+    }
+}
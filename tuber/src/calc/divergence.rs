@@ -0,0 +1,144 @@
+use crate::expr::Expr;
+use std::collections::HashMap;
+
+/// 簡約の途中に現れた式を記録し、以前と同じ式が再び現れたことを検出する
+///
+/// 式はα同値を区別しないハッシュ値で分類し、ハッシュ値が衝突した場合は式どうしをα同値で比べる
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CycleDetector {
+    seen: HashMap<u64, Vec<(Expr, usize)>>,
+}
+
+impl CycleDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// step ステップ目の式を記録する
+    ///
+    /// 以前に同じ式が現れていれば、そのステップ数を返す
+    pub fn observe(&mut self, step: usize, expr: &Expr) -> Option<usize> {
+        let seen = self.seen.entry(expr.alpha_hash()).or_default();
+        match seen.iter().find(|(other, _)| other.alpha_eq(expr)) {
+            Some((_, cycle_start)) => Some(*cycle_start),
+            None => {
+                seen.push((expr.clone(), step));
+                None
+            }
+        }
+    }
+}
+
+// ========================================================================== //
+
+/// likely_non_terminating() で式の大きさを比べる区間の数
+const SEGMENTS: usize = 4;
+
+/// 簡約の途中の式の大きさを記録し、停止しない可能性が高いかどうかを推定する
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GrowthMonitor {
+    sizes: Vec<usize>,
+}
+
+impl GrowthMonitor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&mut self, expr: &Expr) {
+        self.sizes.push(size(expr));
+    }
+
+    /// 式の大きさが単調に増え続けているかどうか
+    ///
+    /// 記録の後半を区間に分け、各区間での最小の大きさが区間ごとに増えていれば停止しない可能性が高いとみなす
+    /// 1 ステップごとの大きさは増減を繰り返すので、区間の最小値で比べる
+    pub fn likely_non_terminating(&self) -> bool {
+        let latter = &self.sizes[self.sizes.len() / 2..];
+        if latter.len() < SEGMENTS * 2 {
+            return false;
+        }
+
+        let minima: Vec<usize> = latter
+            .chunks(latter.len() / SEGMENTS)
+            .take(SEGMENTS)
+            .filter_map(|chunk| chunk.iter().min().copied())
+            .collect();
+
+        minima.windows(2).all(|pair| pair[0] < pair[1])
+    }
+}
+
+/// 式を構成するノードの個数
+fn size(expr: &Expr) -> usize {
+    match expr {
+        Expr::Variable(_) | Expr::Symbol(_) => 1,
+        Expr::Apply { lhs, rhs } => 1 + size(lhs) + size(rhs),
+        Expr::Lambda { body, .. } => 1 + size(body),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_cycle_detector() {
+        let mut detector = CycleDetector::new();
+
+        assert_eq!(detector.observe(0, &expr::l("x", expr::a("x", "x"))), None);
+        assert_eq!(detector.observe(1, &expr::a(":a", ":b")), None);
+
+        // α同値な式は同じ式として扱う
+        assert_eq!(
+            detector.observe(2, &expr::l("y", expr::a("y", "y"))),
+            Some(0)
+        );
+        assert_eq!(detector.observe(3, &expr::a(":a", ":b")), Some(1));
+    }
+
+    #[test]
+    fn test_cycle_detector_hash_collision() {
+        let mut detector = CycleDetector::new();
+
+        // ハッシュ値が衝突しても、α同値でない式は別の式として扱う
+        let hash = expr::s("a").alpha_hash();
+        detector.seen.insert(hash, vec![(expr::s("b"), 0)]);
+        assert_eq!(detector.observe(1, &expr::s("a")), None);
+        assert_eq!(detector.observe(2, &expr::s("a")), Some(1));
+    }
+
+    #[test]
+    fn test_growth_monitor() {
+        let mut monitor = GrowthMonitor::new();
+        let mut e = expr::s("x");
+        for _ in 0..32 {
+            monitor.observe(&e);
+            e = expr::a(":f", e);
+        }
+        assert!(monitor.likely_non_terminating());
+
+        // 記録が少なすぎる場合は判断しない
+        let mut monitor = GrowthMonitor::new();
+        monitor.observe(&expr::s("x"));
+        monitor.observe(&expr::a(":f", ":x"));
+        assert!(!monitor.likely_non_terminating());
+
+        // 大きさが変わらない場合
+        let mut monitor = GrowthMonitor::new();
+        for _ in 0..32 {
+            monitor.observe(&expr::a(":f", ":x"));
+        }
+        assert!(!monitor.likely_non_terminating());
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(size(&expr::s("x")), 1);
+        assert_eq!(size(&expr::a(":f", ":x")), 3);
+        assert_eq!(size(&expr::l("x", expr::a("x", "x"))), 4);
+    }
+}
@@ -0,0 +1,307 @@
+use super::evaluate::Eval;
+use super::strategy::Strategy;
+use crate::context::Context;
+use crate::error::Error;
+use crate::expr::{self, Expr, Identifier};
+
+/// 正規形を求めるときに潜るラムダ抽象と関数適用の深さの上限
+///
+/// 正規形を持たない式は簡約するたびに深くなり続けることがあるので、スタックを使い切る前に打ち切る
+const DEPTH_LIMIT: usize = 256;
+
+/// 2つの式が同じ項を表すかどうかの判定結果
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Equivalence {
+    /// 正規形がα同値
+    Equal,
+
+    /// 正規形がα同値ではない
+    NotEqual,
+
+    /// ステップ数の上限までに正規形が得られず、判定できない
+    Unknown,
+}
+
+/// 2つの式を正規形まで簡約し、α同値かどうかを判定する
+///
+/// 両辺の簡約に要するステップ数の合計が step_limit を超えた場合は Unknown を返す
+pub fn equivalence(
+    context: &Context,
+    lhs: Expr,
+    rhs: Expr,
+    step_limit: usize,
+) -> Result<Equivalence, Error> {
+    let mut normalizer = Normalizer::new(context, step_limit);
+
+    let lhs = match normalizer.normalize(lhs)? {
+        Some(lhs) => lhs,
+        None => return Ok(Equivalence::Unknown),
+    };
+    let rhs = match normalizer.normalize(rhs)? {
+        Some(rhs) => rhs,
+        None => return Ok(Equivalence::Unknown),
+    };
+
+    if lhs.alpha_eq(&rhs) {
+        Ok(Equivalence::Equal)
+    } else {
+        Ok(Equivalence::NotEqual)
+    }
+}
+
+/// ラムダ抽象の内側や部分適用された関数も含めて、式を正規形まで簡約する
+///
+/// ステップ数の上限までに正規形が得られなかった場合は None を返す
+pub fn normalize(context: &Context, expr: Expr, step_limit: usize) -> Result<Option<Expr>, Error> {
+    Ok(normalize_with_steps(context, expr, step_limit)?.map(|(expr, _)| expr))
+}
+
+/// normalize() と同じく式を正規形まで簡約し、正規形と簡約に要したステップ数を返す
+pub fn normalize_with_steps(
+    context: &Context,
+    expr: Expr,
+    step_limit: usize,
+) -> Result<Option<(Expr, usize)>, Error> {
+    let mut normalizer = Normalizer::new(context, step_limit);
+    let expr = normalizer.normalize(expr)?;
+    Ok(expr.map(|expr| (expr, step_limit - normalizer.budget)))
+}
+
+/// ラムダ抽象の内側や部分適用された関数も含めて、式を正規形まで簡約する
+///
+/// Eval はラムダ抽象の内側を簡約しないので、束縛変数を新しいシンボルに置き換えてから簡約し
+/// 簡約した後でシンボルを束縛変数に戻す
+/// 引数が足りない関数は新しいシンボルを引数として与えて簡約する (η展開)
+struct Normalizer<'a> {
+    context: &'a Context,
+
+    /// 残りのステップ数
+    budget: usize,
+
+    /// 次に作るシンボルの番号
+    fresh: usize,
+
+    /// 現在の再帰の深さ
+    depth: usize,
+}
+
+impl<'a> Normalizer<'a> {
+    fn new(context: &'a Context, step_limit: usize) -> Self {
+        Self {
+            context,
+            budget: step_limit,
+            fresh: 0,
+            depth: 0,
+        }
+    }
+
+    /// Eval で簡約できるところまで簡約する、ステップ数の上限に達した場合や簡約が停止しない場合は None
+    ///
+    /// normalize() は深く再帰するので、Eval をスタックに残さないよう別の関数に分けている
+    fn reduce(&mut self, expr: Expr) -> Result<Option<Expr>, Error> {
+        let mut eval = Eval::new(self.context.clone(), expr, Strategy::NormalOrder)
+            .with_step_limit(self.budget)
+            .with_cycle_detection();
+
+        for step in eval.by_ref() {
+            match step {
+                Ok(_) => {}
+                Err(Error::StepLimitExceeded(_)) | Err(Error::Diverged { .. }) => return Ok(None),
+                Err(err) => return Err(err),
+            }
+        }
+        self.budget -= eval.step();
+
+        Ok(Some(eval.expr()))
+    }
+
+    /// 正規形を返す、ステップ数や深さの上限に達した場合や簡約が停止しない場合は None
+    fn normalize(&mut self, expr: Expr) -> Result<Option<Expr>, Error> {
+        if self.depth >= DEPTH_LIMIT {
+            return Ok(None);
+        }
+
+        self.depth += 1;
+        let result = self.normalize_(expr);
+        self.depth -= 1;
+        result
+    }
+
+    fn normalize_(&mut self, expr: Expr) -> Result<Option<Expr>, Error> {
+        let expr = match self.reduce(expr)? {
+            Some(expr) => expr,
+            None => return Ok(None),
+        };
+
+        match expr {
+            Expr::Lambda { param, body } => {
+                let id = self.fresh_id();
+                let mut body = *body;
+                body.substitute(&param, &expr::s(id.clone()));
+
+                Ok(self
+                    .normalize(body)?
+                    .map(|body| expr::l(id.clone(), bind(body, &id))))
+            }
+
+            expr => {
+                let (callee, args) = expr.unapply();
+
+                if let Expr::Variable(id) = callee {
+                    if let Some(func) = self.context.get(id) {
+                        // 引数をとらない関数は展開する
+                        if func.arity() == 0 && args.is_empty() {
+                            if self.budget == 0 {
+                                return Ok(None);
+                            }
+                            self.budget -= 1;
+                            return self.normalize(func.apply(Vec::new()));
+                        }
+
+                        // 引数が足りない関数は新しいシンボルを与えて簡約する
+                        if func.arity() > args.len() {
+                            let ids: Vec<Identifier> = (args.len()..func.arity())
+                                .map(|_| self.fresh_id())
+                                .collect();
+                            let applied = ids
+                                .iter()
+                                .fold(expr.clone(), |acc, id| expr::a(acc, expr::s(id.clone())));
+
+                            return Ok(self.normalize(applied)?.map(|body| {
+                                ids.iter()
+                                    .rev()
+                                    .fold(body, |acc, id| expr::l(id.clone(), bind(acc, id)))
+                            }));
+                        }
+                    }
+                }
+
+                let mut normalized = callee.clone();
+                for arg in args {
+                    match self.normalize(arg.clone())? {
+                        Some(arg) => normalized = expr::a(normalized, arg),
+                        None => return Ok(None),
+                    }
+                }
+                Ok(Some(normalized))
+            }
+        }
+    }
+
+    fn fresh_id(&mut self) -> Identifier {
+        // 識別子として構文解析できない名前にして、既存の識別子と衝突しないようにする
+        let id = Identifier::from(format!("#{}", self.fresh));
+        self.fresh += 1;
+        id
+    }
+}
+
+/// シンボル id を同名の変数に置き換える
+fn bind(expr: Expr, id: &Identifier) -> Expr {
+    match expr {
+        Expr::Symbol(symbol) if &symbol == id => Expr::Variable(symbol),
+        Expr::Apply { lhs, rhs } => expr::a(bind(*lhs, id), bind(*rhs, id)),
+        Expr::Lambda { param, body } => expr::l(param, bind(*body, id)),
+        expr => expr,
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::func;
+
+    fn setup() -> Context {
+        let i = func::new("i", vec!["x"], "x");
+        let k = func::new("k", vec!["x", "y"], "x");
+        let s = func::new(
+            "s",
+            vec!["x", "y", "z"],
+            expr::a(expr::a("x", "z"), expr::a("y", "z")),
+        );
+        let omega = func::new("OMEGA", Vec::<&str>::new(), expr::a(expr::a("s", "i"), "i"));
+
+        Context::from(vec![i, k, s, omega])
+    }
+
+    #[test]
+    fn test_equivalence() {
+        let context = setup();
+
+        // ```skk:a と `i:a はどちらも :a に簡約される
+        let lhs = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
+        let rhs = expr::a("i", ":a");
+        assert_eq!(equivalence(&context, lhs, rhs, 100), Ok(Equivalence::Equal));
+
+        // ``skk と i は引数を与えれば同じ振る舞いをする
+        let lhs = expr::a(expr::a("s", "k"), "k");
+        assert_eq!(
+            equivalence(&context, lhs, expr::v("i"), 100),
+            Ok(Equivalence::Equal)
+        );
+
+        // 関数とラムダ抽象も比較できる
+        let lhs = expr::v("k");
+        let rhs = expr::l("a", expr::l("b", "a"));
+        assert_eq!(equivalence(&context, lhs, rhs, 100), Ok(Equivalence::Equal));
+
+        // k と `ki は異なる
+        let lhs = expr::v("k");
+        let rhs = expr::a("k", "i");
+        assert_eq!(
+            equivalence(&context, lhs, rhs, 100),
+            Ok(Equivalence::NotEqual)
+        );
+    }
+
+    #[test]
+    fn test_equivalence_unknown() {
+        let context = setup();
+
+        // ``OMEGA OMEGA は正規形を持たない
+        let lhs = expr::a("OMEGA", "OMEGA");
+        assert_eq!(
+            equivalence(&context, lhs, expr::v("i"), 100),
+            Ok(Equivalence::Unknown)
+        );
+    }
+
+    #[test]
+    fn test_equivalence_church_numerals() {
+        let context = Context::default();
+
+        // ``ADD 2 3 と 5 は同じ数を表す
+        let lhs = expr::a(expr::a("ADD", "2"), "3");
+        assert_eq!(
+            equivalence(&context, lhs, expr::v("5"), 1000),
+            Ok(Equivalence::Equal)
+        );
+
+        // 定義済みの ADD と同じ定義の関数は等しい
+        let add = expr::l(
+            "m",
+            expr::l(
+                "n",
+                expr::l(
+                    "f",
+                    expr::l(
+                        "x",
+                        expr::a(expr::a("m", "f"), expr::a(expr::a("n", "f"), "x")),
+                    ),
+                ),
+            ),
+        );
+        assert_eq!(
+            equivalence(&context, expr::v("ADD"), add, 1000),
+            Ok(Equivalence::Equal)
+        );
+
+        // ADD と MUL は異なる
+        assert_eq!(
+            equivalence(&context, expr::v("ADD"), expr::v("MUL"), 1000),
+            Ok(Equivalence::NotEqual)
+        );
+    }
+}
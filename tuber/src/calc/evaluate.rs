@@ -1,6 +1,9 @@
 use super::apply::apply;
 use super::arity::arity;
+use super::divergence::CycleDetector;
+use super::strategy::Strategy;
 use crate::context::Context;
+use crate::error::Error;
 use crate::expr::{self, Expr, Path, PathBuilder};
 use std::{cmp, iter, slice};
 
@@ -8,49 +11,198 @@ use std::{cmp, iter, slice};
 pub struct Eval {
     context: Context,
     next_path: Option<Path>,
+
+    /// next_path にある簡約基の簡約の種類、簡約のたびに簡約基を探し直さなくて済むよう next_path と一緒に保持する
+    next_kind: StepKind,
     inventory: Inventory,
     step: usize,
+    step_limit: Option<usize>,
+    halted: bool,
+    strategy: Strategy,
+    cycle_detector: Option<CycleDetector>,
+
+    /// β 簡約できる簡約基がなくなった後に η 簡約を行うかどうか
+    eta_reduction: bool,
+
+    /// 次に簡約を進めようとしたときに返すエラー
+    pending_error: Option<Error>,
 }
 
 impl Eval {
-    pub fn new(context: Context, expr: Expr) -> Self {
+    pub fn new(context: Context, expr: Expr, strategy: Strategy) -> Self {
         let inventory = Inventory::new(&context, expr);
         Self {
             context,
-            next_path: inventory.next_path(),
+            next_path: inventory.next_path(&strategy),
+            next_kind: StepKind::Beta,
             inventory,
+            strategy,
             step: 0,
+            step_limit: None,
+            halted: false,
+            cycle_detector: None,
+            eta_reduction: false,
+            pending_error: None,
+        }
+    }
+
+    /// β 簡約できる簡約基がなくなった後に、η 簡約基 ^x.`Mx を M に簡約するようにする
+    ///
+    /// η 簡約のステップは EvalStep::kind が StepKind::Eta になる
+    pub fn with_eta_reduction(self) -> Self {
+        let mut eval = Self {
+            eta_reduction: true,
+            ..self
+        };
+        eval.update_next();
+        eval
+    }
+
+    /// 以前と同じ式 (α同値を区別しない) が再び現れたら簡約を打ち切るようにする
+    ///
+    /// 式が再び現れたステップを返した後に簡約を進めようとすると Error::Diverged を返す
+    pub fn with_cycle_detection(self) -> Self {
+        let mut cycle_detector = CycleDetector::new();
+        cycle_detector.observe(self.step, &self.expr());
+        Self {
+            cycle_detector: Some(cycle_detector),
+            ..self
         }
     }
 
+    /// 簡約のステップ数に上限を設ける
+    ///
+    /// 上限に達した後に簡約を進めようとすると Error::StepLimitExceeded を返す
+    pub fn with_step_limit(self, step_limit: usize) -> Self {
+        Self {
+            step_limit: Some(step_limit),
+            ..self
+        }
+    }
+
+    /// step ステップ目の式 expr から、同じ Context と設定で簡約をやり直す Eval を作る
+    ///
+    /// それまでの式の記録は引き継がないので、循環の検出は行わない
+    pub fn resume(&self, step: usize, expr: Expr) -> Self {
+        let mut eval = Self {
+            step,
+            step_limit: self.step_limit,
+            eta_reduction: self.eta_reduction,
+            ..Self::new(self.context.clone(), expr, self.strategy)
+        };
+        eval.update_next();
+        eval
+    }
+
     pub fn next_path(&self) -> Option<Path> {
         self.next_path.clone()
     }
+
+    /// 次に簡約される簡約基の位置を返す、next_path() と同じ
+    pub fn reducible_path(&self) -> Option<Path> {
+        self.next_path()
+    }
+
+    /// 現在の式を返す
+    pub fn expr(&self) -> Expr {
+        Expr::from(&self.inventory)
+    }
+
+    /// これまでに簡約したステップ数を返す
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// 次に簡約する簡約基を探し、next_path と next_kind を更新する
+    fn update_next(&mut self) {
+        match self.find_redex() {
+            Some((path, kind)) => {
+                self.next_path = Some(path);
+                self.next_kind = kind;
+            }
+            None => self.next_path = None,
+        }
+    }
+
+    /// 次に簡約する簡約基の位置と簡約の種類を返す
+    fn find_redex(&self) -> Option<(Path, StepKind)> {
+        if let Some(path) = self.inventory.next_path(&self.strategy) {
+            return Some((path, StepKind::Beta));
+        }
+
+        if self.eta_reduction {
+            let route = self.inventory.eta_route(&self.strategy)?;
+            let mut builder = PathBuilder::new();
+            for index in route {
+                builder.add_route(index + 1);
+            }
+            builder.set_arity(0);
+            return Some((builder.build(), StepKind::Eta));
+        }
+
+        None
+    }
 }
 
 impl Iterator for Eval {
-    type Item = EvalStep;
+    type Item = Result<EvalStep, Error>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        let reduced_path = self.inventory.next_path();
-        let inventory = self.inventory.get_next()?;
+        if self.halted {
+            return None;
+        }
+
+        if let Some(err) = self.pending_error.take() {
+            self.halted = true;
+            return Some(Err(err));
+        }
+
+        let mut reduced_path = self.next_path.clone()?;
+        let kind = self.next_kind;
+
+        if let Some(step_limit) = self.step_limit {
+            if self.step >= step_limit {
+                self.halted = true;
+                return Some(Err(Error::StepLimitExceeded(step_limit)));
+            }
+        }
+
+        let result = match kind {
+            StepKind::Beta => self.inventory.eval_next(&self.context, &self.strategy)?,
+            StepKind::Eta => self.inventory.eta_next(&self.context, &reduced_path)?,
+        };
 
-        match inventory.eval(&self.context) {
-            Some(num_args) => {
-                let inventory = self.inventory.clone();
-                let mut reduced_path = reduced_path.unwrap();
+        match result {
+            Ok(num_args) => {
                 reduced_path.set_arity(num_args);
-                let next_path = inventory.next_path();
-                let expr = inventory.into();
+                self.update_next();
+                let next_path = self.next_path.clone();
+                let expr = Expr::from(&self.inventory);
                 self.step += 1;
-                Some(EvalStep {
+
+                if let Some(cycle_detector) = &mut self.cycle_detector {
+                    if let Some(cycle_start) = cycle_detector.observe(self.step, &expr) {
+                        self.pending_error = Some(Error::Diverged {
+                            cycle_start,
+                            period: self.step - cycle_start,
+                        });
+                    }
+                }
+
+                Some(Ok(EvalStep {
                     expr,
                     step: self.step,
                     reduced_path,
                     next_path,
-                })
+                    kind,
+                }))
+            }
+            Err(err) => {
+                // 簡約基を取り出した後に失敗しているので、これ以上簡約を続けることはできない
+                self.halted = true;
+                self.next_path = None;
+                Some(Err(err))
             }
-            None => None,
         }
     }
 }
@@ -62,6 +214,19 @@ struct Inventory {
     callee: Expr,
     arity: Option<usize>,
     args: Args,
+
+    /// 自身または引数の中に簡約基を含むかどうか
+    ///
+    /// 簡約のたびに木全体を辿り直さなくて済むよう、簡約した位置から根までの経路上でのみ更新する
+    reducible: bool,
+
+    /// 簡約基を含む最初の引数の位置
+    ///
+    /// reducible と同じく簡約した位置から根までの経路上でのみ更新し、簡約基を探すときに引数を走査しなくて済むようにする
+    reducible_arg: Option<usize>,
+
+    /// 先頭が簡約基になっている最初の引数の位置
+    callable_arg: Option<usize>,
 }
 
 impl Inventory {
@@ -76,68 +241,186 @@ impl Inventory {
 
         let arity = arity(context, &callee).filter(|arity| args.len() >= cmp::max(1, *arity));
 
-        Self {
+        let mut inventory = Self {
             callee,
             arity,
             args,
+            reducible: false,
+            reducible_arg: None,
+            callable_arg: None,
+        };
+        inventory.update_cache();
+        inventory
+    }
+
+    /// 全ての引数のキャッシュを元に、自身のキャッシュを計算し直す
+    fn update_cache(&mut self) {
+        self.reducible_arg = self.find_arg(0, Inventory::is_reducible);
+        self.callable_arg = self.find_arg(0, Inventory::is_callable);
+        self.reducible = self.arity.is_some() || self.reducible_arg.is_some();
+    }
+
+    /// index 番目の引数だけが変わったときに、自身のキャッシュを更新する
+    fn update_cache_at(&mut self, index: usize) {
+        self.reducible_arg = self.leftmost(self.reducible_arg, index, Inventory::is_reducible);
+        self.callable_arg = self.leftmost(self.callable_arg, index, Inventory::is_callable);
+        self.reducible = self.arity.is_some() || self.reducible_arg.is_some();
+    }
+
+    /// index 番目の引数だけが変わったときの、predicate を満たす最初の引数の位置
+    ///
+    /// 変わった引数が predicate を満たさなくなった場合だけ、それより後ろの引数を走査する
+    fn leftmost(
+        &self,
+        current: Option<usize>,
+        index: usize,
+        predicate: fn(&Inventory) -> bool,
+    ) -> Option<usize> {
+        match self.args.get(index) {
+            Some(arg) if predicate(arg) => Some(current.map_or(index, |c| cmp::min(c, index))),
+            _ if current == Some(index) => self.find_arg(index + 1, predicate),
+            _ => current,
         }
     }
 
-    fn reducible(&self) -> bool {
-        let reducible_callee = || self.arity.is_some();
-        let reducible_args = || self.args.iter().any(|arg| arg.reducible()); // TODO: reducible() が呼び出されるたびに args.iter() を再帰的に辿っていくのが非効率かもしれない、計算結果をキャッシュする機構を考えたい
-        reducible_callee() || reducible_args()
+    /// from 番目以降の引数のうち、predicate を満たす最初の引数の位置を返す
+    fn find_arg(&self, from: usize, predicate: fn(&Inventory) -> bool) -> Option<usize> {
+        self.args
+            .enumerate()
+            .skip(from)
+            .find(|(_index, arg)| predicate(arg))
+            .map(|(index, _arg)| index)
     }
 
-    fn next_path(&self) -> Option<Path> {
+    fn is_reducible(&self) -> bool {
+        self.reducible
+    }
+
+    fn is_callable(&self) -> bool {
+        self.arity.is_some()
+    }
+
+    /// 評価戦略に従って次に簡約する簡約基の位置を選ぶ
+    ///
+    /// 引数の位置はキャッシュから引くので、引数を走査しない
+    fn select(&self, strategy: &Strategy) -> Option<Selection> {
+        // 先頭から n 個の引数に収まる位置だけを選ぶ
+        let within =
+            |arg: Option<usize>, n: usize| arg.filter(|index| *index < n).map(Selection::Arg);
+
+        match (strategy, self.arity) {
+            (Strategy::NormalOrder, Some(_)) => Some(Selection::Callee),
+            (Strategy::NormalOrder, None) => within(self.reducible_arg, self.args.len()),
+
+            // 簡約基が消費する引数を先に簡約し尽くす
+            (Strategy::ApplicativeOrder, Some(arity)) => {
+                within(self.reducible_arg, arity).or(Some(Selection::Callee))
+            }
+            (Strategy::ApplicativeOrder, None) => within(self.reducible_arg, self.args.len()),
+
+            (Strategy::CallByName, Some(_)) => Some(Selection::Callee),
+
+            // 簡約基が消費する引数を先に弱頭部正規形まで簡約する
+            (Strategy::CallByValue, Some(arity)) => {
+                within(self.callable_arg, arity).or(Some(Selection::Callee))
+            }
+
+            // 先頭が簡約基でなければ弱頭部正規形に達しているので、引数の中までは簡約しない
+            (Strategy::CallByName | Strategy::CallByValue, None) => None,
+        }
+    }
+
+    fn next_path(&self, strategy: &Strategy) -> Option<Path> {
         let mut builder = PathBuilder::new();
         let mut inventory = self;
         loop {
-            if let Some(arity) = inventory.arity {
-                builder.set_arity(arity);
-                let path = builder.build();
-                return Some(path);
-            } else {
-                match inventory
-                    .args
-                    .enumerate()
-                    .find(|(_index, arg)| arg.reducible())
-                {
-                    Some((index, arg)) => {
-                        builder.add_route(index + 1);
-                        inventory = arg;
-                    }
-                    None => return None,
+            match inventory.select(strategy)? {
+                Selection::Callee => {
+                    builder.set_arity(inventory.arity?);
+                    return Some(builder.build());
+                }
+                Selection::Arg(index) => {
+                    builder.add_route(index + 1);
+                    inventory = inventory.args.get(index)?;
                 }
             }
         }
     }
 
-    fn get_next(&mut self) -> Option<&mut Inventory> {
-        let mut inventory = self;
-        loop {
-            if inventory.arity.is_some() {
-                return Some(inventory);
-            } else {
-                match inventory
-                    .args
-                    .enumerate_mut()
-                    .find(|(_index, arg)| arg.reducible())
-                {
-                    Some((_index, arg)) => {
-                        inventory = arg;
-                    }
-                    None => return None,
-                }
+    /// 評価戦略に従って次の簡約基を簡約する
+    ///
+    /// 簡約した位置から根に向かって reducible と引数の位置のキャッシュを更新する
+    fn eval_next(
+        &mut self,
+        context: &Context,
+        strategy: &Strategy,
+    ) -> Option<Result<usize, Error>> {
+        let result = match self.select(strategy)? {
+            Selection::Callee => {
+                let result = self.eval(context);
+                self.update_cache();
+                result
+            }
+            Selection::Arg(index) => {
+                let result = self.args.get_mut(index)?.eval_next(context, strategy)?;
+                self.update_cache_at(index);
+                result
             }
+        };
+        Some(result)
+    }
+
+    /// η 簡約できる簡約基までの引数の位置 (0 始まり) を返す
+    ///
+    /// 引数をとらないラムダ抽象だけが η 簡約の対象になる
+    /// 弱頭部正規形までしか簡約しない評価戦略では、引数の中までは探さない
+    fn eta_route(&self, strategy: &Strategy) -> Option<Vec<usize>> {
+        if self.args.len() == 0 && self.callee.eta_reduce().is_some() {
+            return Some(Vec::new());
         }
+
+        if let Strategy::CallByName | Strategy::CallByValue = strategy {
+            return None;
+        }
+
+        self.args.enumerate().find_map(|(index, arg)| {
+            let mut route = arg.eta_route(strategy)?;
+            route.insert(0, index);
+            Some(route)
+        })
     }
 
-    fn eval(&mut self, context: &Context) -> Option<usize> {
-        let args = self.args.drain(self.arity?)?;
+    /// path の位置にある η 簡約基を簡約し、簡約した結果の式に与えられている引数の個数を返す
+    fn eta_next(&mut self, context: &Context, path: &Path) -> Option<Result<usize, Error>> {
+        let result = match path {
+            Path::Arg(index, next) => {
+                let result = self.args.get_mut(index - 1)?.eta_next(context, next)?;
+                self.update_cache_at(index - 1);
+                result
+            }
+            Path::Callee(_) => {
+                let reduced = self.callee.eta_reduce()?.clone();
+                *self = Inventory::new(context, reduced);
+                Ok(self.args.len())
+            }
+        };
+        Some(result)
+    }
+
+    fn eval(&mut self, context: &Context) -> Result<usize, Error> {
+        let callee_arity = self
+            .arity
+            .ok_or_else(|| Error::NotAFunction(self.callee.to_owned()))?;
+        let args = self
+            .args
+            .drain(callee_arity)
+            .ok_or_else(|| Error::ArityMismatch {
+                expected: callee_arity,
+                actual: self.args.len(),
+            })?;
         let mut callee = &mut self.callee;
 
-        apply(context, callee, args).ok()?;
+        apply(context, callee, args)?;
 
         let mut num_args = 0;
 
@@ -151,10 +434,19 @@ impl Inventory {
         self.arity =
             arity(context, &self.callee).filter(|arity| self.args.len() >= cmp::max(1, *arity));
 
-        Some(num_args)
+        Ok(num_args)
     }
 }
 
+/// 次に簡約する簡約基の位置
+enum Selection {
+    /// Inventory 自身の先頭が簡約基
+    Callee,
+
+    /// index 番目の引数の中に簡約基がある
+    Arg(usize),
+}
+
 impl From<Inventory> for Expr {
     fn from(inventory: Inventory) -> Self {
         let mut expr = inventory.callee;
@@ -165,6 +457,16 @@ impl From<Inventory> for Expr {
     }
 }
 
+impl From<&Inventory> for Expr {
+    fn from(inventory: &Inventory) -> Self {
+        let mut expr = inventory.callee.clone();
+        for arg in inventory.args.0.iter().rev() {
+            expr = expr::a(expr, Expr::from(arg));
+        }
+        expr
+    }
+}
+
 // ========================================================================== //
 
 /// ラムダ式の部分式のうち引数部分を保持する両端キュー
@@ -187,6 +489,16 @@ impl Args {
         self.0.push(inventory)
     }
 
+    fn get(&self, index: usize) -> Option<&Inventory> {
+        let length = self.len();
+        self.0.get(length.checked_sub(index + 1)?)
+    }
+
+    fn get_mut(&mut self, index: usize) -> Option<&mut Inventory> {
+        let length = self.len();
+        self.0.get_mut(length.checked_sub(index + 1)?)
+    }
+
     fn drain(&mut self, n: usize) -> Option<Vec<Expr>> {
         let length = self.len();
 
@@ -204,17 +516,9 @@ impl Args {
     }
 
     // TODO: ここから下もっとどうにかしたい
-    fn iter(&self) -> ArgsIter {
-        ArgsIter::new(self.to_owned())
-    }
-
     fn enumerate(&self) -> iter::Enumerate<iter::Rev<slice::Iter<'_, Inventory>>> {
         self.0.iter().rev().enumerate()
     }
-
-    fn enumerate_mut(&mut self) -> iter::Enumerate<iter::Rev<slice::IterMut<'_, Inventory>>> {
-        self.0.iter_mut().rev().enumerate()
-    }
 }
 
 impl From<Vec<Inventory>> for Args {
@@ -223,34 +527,26 @@ impl From<Vec<Inventory>> for Args {
     }
 }
 
-struct ArgsIter {
-    iter: std::iter::Rev<std::vec::IntoIter<Inventory>>,
-}
-
-impl ArgsIter {
-    fn new(args: Args) -> Self {
-        Self {
-            iter: args.0.into_iter().rev(),
-        }
-    }
-}
-
-impl Iterator for ArgsIter {
-    type Item = Inventory;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
-    }
-}
-
 // ========================================================================== //
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct EvalStep {
     pub step: usize,
     pub expr: Expr,
     pub reduced_path: Path,
     pub next_path: Option<Path>,
+    pub kind: StepKind,
+}
+
+/// 簡約の種類
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum StepKind {
+    /// 関数適用またはラムダ抽象への適用を簡約した (β 簡約)
+    #[default]
+    Beta,
+
+    /// ^x.`Mx を M に簡約した (η 簡約)
+    Eta,
 }
 
 // ========================================================================== //
@@ -281,7 +577,7 @@ mod tests {
         let context = setup();
 
         let expr = expr::a(":g", expr::a(":f", expr::a("i", ":y")));
-        let eval = Eval::new(context.clone(), expr);
+        let eval = Eval::new(context.clone(), expr, Strategy::NormalOrder);
 
         assert_eq!(
             eval.inventory,
@@ -298,9 +594,21 @@ mod tests {
                             callee: expr::s("y"),
                             arity: None,
                             args: Args::new(),
+                            reducible: false,
+                            reducible_arg: None,
+                            callable_arg: None,
                         },]),
+                        reducible: true,
+                        reducible_arg: None,
+                        callable_arg: None,
                     }]),
+                    reducible: true,
+                    reducible_arg: Some(0),
+                    callable_arg: Some(0),
                 },]),
+                reducible: true,
+                reducible_arg: Some(0),
+                callable_arg: None,
             }
         );
     }
@@ -311,46 +619,77 @@ mod tests {
 
         let expr = expr::s("TRUE");
         let inventory = Inventory::new(&context, expr);
-        assert_eq!(inventory.next_path(), None);
+        assert_eq!(inventory.next_path(&Strategy::NormalOrder), None);
 
         let expr = expr::v("TRUE");
         let inventory = Inventory::new(&context, expr);
-        assert_eq!(inventory.next_path().map(Vec::<usize>::from), None);
+        assert_eq!(
+            inventory
+                .next_path(&Strategy::NormalOrder)
+                .map(Vec::<usize>::from),
+            None
+        );
 
         let expr = expr::a(":i", ":x");
         let inventory = Inventory::new(&context, expr);
-        assert_eq!(inventory.next_path().map(Vec::<usize>::from), None);
+        assert_eq!(
+            inventory
+                .next_path(&Strategy::NormalOrder)
+                .map(Vec::<usize>::from),
+            None
+        );
 
         let expr = expr::a("i", ":x");
         let inventory = Inventory::new(&context, expr);
-        assert_eq!(inventory.next_path().map(Vec::<usize>::from), Some(vec![1]));
+        assert_eq!(
+            inventory
+                .next_path(&Strategy::NormalOrder)
+                .map(Vec::<usize>::from),
+            Some(vec![1])
+        );
 
         let expr = expr::a(expr::a("i", ":x"), ":y");
         let inventory = Inventory::new(&context, expr);
-        assert_eq!(inventory.next_path().map(Vec::<usize>::from), Some(vec![1]));
+        assert_eq!(
+            inventory
+                .next_path(&Strategy::NormalOrder)
+                .map(Vec::<usize>::from),
+            Some(vec![1])
+        );
 
         let expr = expr::a(":f", expr::a("i", ":x"));
         let inventory = Inventory::new(&context, expr);
         assert_eq!(
-            inventory.next_path().map(Vec::<usize>::from),
+            inventory
+                .next_path(&Strategy::NormalOrder)
+                .map(Vec::<usize>::from),
             Some(vec![1, 1])
         );
 
         let expr = expr::a(expr::a("i", ":x"), expr::a("i", ":y"));
         let inventory = Inventory::new(&context, expr);
-        assert_eq!(inventory.next_path().map(Vec::<usize>::from), Some(vec![1]));
+        assert_eq!(
+            inventory
+                .next_path(&Strategy::NormalOrder)
+                .map(Vec::<usize>::from),
+            Some(vec![1])
+        );
 
         let expr = expr::a(expr::a(":i", ":x"), expr::a("i", ":y"));
         let inventory = Inventory::new(&context, expr);
         assert_eq!(
-            inventory.next_path().map(Vec::<usize>::from),
+            inventory
+                .next_path(&Strategy::NormalOrder)
+                .map(Vec::<usize>::from),
             Some(vec![2, 1])
         );
 
         let expr = expr::a(":g", expr::a(":f", expr::a("i", ":y")));
         let inventory = Inventory::new(&context, expr);
         assert_eq!(
-            inventory.next_path().map(Vec::<usize>::from),
+            inventory
+                .next_path(&Strategy::NormalOrder)
+                .map(Vec::<usize>::from),
             Some(vec![1, 1, 1])
         );
     }
@@ -362,10 +701,13 @@ mod tests {
         let i = expr::l("x", "x");
         let expr = expr::a(i, ":a");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
-        assert_eq!(eval.next().map(|step| step.expr), Some(expr::s("a")));
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(expr::s("a"))
+        );
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -375,10 +717,13 @@ mod tests {
         let k = expr::l("x", expr::l("y", "x"));
         let expr = expr::a(k, ":a");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
-        assert_eq!(eval.next().map(|step| step.expr), Some(expr::l("y", ":a")));
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(expr::l("y", ":a"))
+        );
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -388,14 +733,17 @@ mod tests {
         let k = expr::l("x", expr::l("y", "x"));
         let expr = expr::a(expr::a(k, ":a"), ":b");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
         assert_eq!(
-            eval.next().map(|step| step.expr),
+            eval.next().map(|step| step.unwrap().expr),
             Some(expr::a(expr::l("y", ":a"), ":b"))
         );
-        assert_eq!(eval.next().map(|step| step.expr), Some(":a".into()));
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(":a".into())
+        );
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -404,9 +752,9 @@ mod tests {
 
         let expr = expr::v("TRUE");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -415,9 +763,9 @@ mod tests {
 
         let expr = expr::a(":a", "TRUE");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -426,15 +774,21 @@ mod tests {
 
         let expr = expr::a(expr::a("TRUE", ":a"), ":b");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
         assert_eq!(
-            eval.next().map(|step| step.expr),
+            eval.next().map(|step| step.unwrap().expr),
             Some(expr::a(expr::a(expr::a("k", "i"), ":a"), ":b"))
         );
-        assert_eq!(eval.next().map(|step| step.expr), Some(expr::a("i", ":b")));
-        assert_eq!(eval.next().map(|step| step.expr), Some(":b".into()));
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(expr::a("i", ":b"))
+        );
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(":b".into())
+        );
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -443,10 +797,13 @@ mod tests {
 
         let expr = expr::a("i", ":a");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
-        assert_eq!(eval.next().map(|step| step.expr), Some(":a".into()));
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(":a".into())
+        );
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -455,10 +812,10 @@ mod tests {
 
         let expr = expr::a("k", ":a");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
         // k の arity が2なのに対して引数を1つしか与えていないので簡約されない
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -467,10 +824,13 @@ mod tests {
 
         let expr = expr::a(expr::a("k", ":a"), ":b");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
-        assert_eq!(eval.next().map(|step| step.expr), Some(":a".into()));
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(":a".into())
+        );
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -479,10 +839,10 @@ mod tests {
 
         let expr = expr::a("s", ":a");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
         // s の arity が3なのに対して引数を1つしか与えていないので簡約されない
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -491,10 +851,10 @@ mod tests {
 
         let expr = expr::a(expr::a("s", ":a"), ":b");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
         // s の arity が3なのに対して引数を2つしか与えていないので簡約されない
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -503,13 +863,13 @@ mod tests {
 
         let expr = expr::a(expr::a(expr::a("s", ":a"), ":b"), ":c");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
         assert_eq!(
-            eval.next().map(|step| step.expr),
+            eval.next().map(|step| step.unwrap().expr),
             Some(expr::a(expr::a(":a", ":c"), expr::a(":b", ":c")))
         );
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -518,9 +878,12 @@ mod tests {
 
         let expr = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
 
-        let eval = Eval::new(context, expr);
+        let eval = Eval::new(context, expr, Strategy::NormalOrder);
 
-        assert_eq!(eval.last().map(|step| step.expr), Some(":a".into()));
+        assert_eq!(
+            eval.last().map(|step| step.unwrap().expr),
+            Some(":a".into())
+        );
     }
 
     #[test]
@@ -530,10 +893,13 @@ mod tests {
         // `:a``k:b:c
         let expr = expr::a(expr::s("a"), expr::a(expr::a("k", ":b"), ":c"));
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
-        assert_eq!(eval.next().map(|step| step.expr), Some(expr::a(":a", ":b")));
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(expr::a(":a", ":b"))
+        );
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -543,17 +909,17 @@ mod tests {
         // ```:a`i:b`i:c
         let expr = expr::a(expr::a(":a", expr::a("i", ":b")), expr::a("i", ":c"));
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
         assert_eq!(
-            eval.next().map(|step| step.expr),
+            eval.next().map(|step| step.unwrap().expr),
             Some(expr::a(expr::a(":a", ":b"), expr::a("i", ":c")))
         );
         assert_eq!(
-            eval.next().map(|step| step.expr),
+            eval.next().map(|step| step.unwrap().expr),
             Some(expr::a(expr::a(":a", ":b"), ":c"))
         );
-        assert_eq!(eval.next().map(|step| step.expr), None);
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), None);
     }
 
     #[test]
@@ -569,10 +935,10 @@ mod tests {
             ":c",
         );
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
         assert_eq!(
-            eval.next().map(|step| step.expr),
+            eval.next().map(|step| step.unwrap().expr),
             // ``^x.`x:a:c`^x.`x:b:c
             Some(expr::a(
                 expr::a(expr::l("x", expr::a("x", ":a")), ":c"),
@@ -580,7 +946,7 @@ mod tests {
             ))
         );
         assert_eq!(
-            eval.next().map(|step| step.expr),
+            eval.next().map(|step| step.unwrap().expr),
             // ``:c:a`^x.`x:b:c
             Some(expr::a(
                 expr::a(":c", ":a"),
@@ -588,7 +954,7 @@ mod tests {
             ))
         );
         assert_eq!(
-            eval.next().map(|step| step.expr),
+            eval.next().map(|step| step.unwrap().expr),
             // ``:c:a`:c:b
             Some(expr::a(expr::a(":c", ":a"), expr::a(":c", ":b")))
         );
@@ -600,19 +966,19 @@ mod tests {
         let context = setup();
         let expr = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
         assert_eq!(
             eval.next_path.as_ref().map(Vec::<usize>::from),
             Some(vec![3])
         );
 
-        let step = eval.next().unwrap();
+        let step = eval.next().unwrap().unwrap();
         assert_eq!(
             step.next_path.as_ref().map(Vec::<usize>::from),
             Some(vec![2])
         );
 
-        let step = eval.next().unwrap();
+        let step = eval.next().unwrap().unwrap();
         assert_eq!(step.next_path.as_ref().map(Vec::<usize>::from), None);
     }
 
@@ -621,25 +987,25 @@ mod tests {
         let context = setup();
         let expr = expr::a(expr::a(expr::a("s", "i"), expr::a("k", ":b")), ":a");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
         assert_eq!(
             eval.next_path.as_ref().map(Vec::<usize>::from),
             Some(vec![3])
         );
 
-        let step = eval.next().unwrap();
+        let step = eval.next().unwrap().unwrap();
         assert_eq!(
             step.next_path.as_ref().map(Vec::<usize>::from),
             Some(vec![1])
         );
 
-        let step = eval.next().unwrap();
+        let step = eval.next().unwrap().unwrap();
         assert_eq!(
             step.next_path.as_ref().map(Vec::<usize>::from),
             Some(vec![1, 2])
         );
 
-        let step = eval.next().unwrap();
+        let step = eval.next().unwrap().unwrap();
         assert_eq!(step.next_path.as_ref().map(Vec::<usize>::from), None);
     }
 
@@ -648,12 +1014,12 @@ mod tests {
         let context = setup();
         let expr = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
-        let step = eval.next().unwrap();
+        let step = eval.next().unwrap().unwrap();
         assert_eq!(Vec::<usize>::from(&step.reduced_path), vec![2]);
 
-        let step = eval.next().unwrap();
+        let step = eval.next().unwrap().unwrap();
         assert_eq!(Vec::<usize>::from(&step.reduced_path), vec![0]);
     }
 
@@ -662,15 +1028,335 @@ mod tests {
         let context = setup();
         let expr = expr::a(expr::a(expr::a("s", "i"), expr::a("k", ":b")), ":a");
 
-        let mut eval = Eval::new(context, expr);
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
 
-        let step = eval.next().unwrap();
+        let step = eval.next().unwrap().unwrap();
         assert_eq!(Vec::<usize>::from(&step.reduced_path), vec![2]);
 
-        let step = eval.next().unwrap();
+        let step = eval.next().unwrap().unwrap();
         assert_eq!(Vec::<usize>::from(&step.reduced_path), vec![0]);
 
-        let step = eval.next().unwrap();
+        let step = eval.next().unwrap().unwrap();
         assert_eq!(Vec::<usize>::from(&step.reduced_path), vec![1, 0]);
     }
+
+    #[test]
+    fn test_eval_step_limit() {
+        let context = setup();
+        let expr = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
+
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder).with_step_limit(1);
+
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(expr::a(expr::a("k", ":a"), expr::a("k", ":a")))
+        );
+        assert_eq!(eval.next(), Some(Err(Error::StepLimitExceeded(1))));
+        assert_eq!(eval.next(), None);
+    }
+
+    #[test]
+    fn test_eval_step_limit_not_reached() {
+        let context = setup();
+        let expr = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
+
+        let eval = Eval::new(context, expr, Strategy::NormalOrder).with_step_limit(2);
+
+        assert_eq!(
+            eval.map(|step| step.map(|step| step.expr))
+                .collect::<Result<Vec<_>, _>>(),
+            Ok(vec![
+                expr::a(expr::a("k", ":a"), expr::a("k", ":a")),
+                expr::s("a")
+            ])
+        );
+    }
+
+    #[test]
+    fn test_eval_applicative_order() {
+        let context = setup();
+
+        // ``k:a`i:b => ``k:a:b => :a
+        let expr = expr::a(expr::a("k", ":a"), expr::a("i", ":b"));
+        let mut eval = Eval::new(context, expr, Strategy::ApplicativeOrder);
+
+        assert_eq!(eval.next_path().map(Vec::<usize>::from), Some(vec![2, 1]));
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(expr::a(expr::a("k", ":a"), ":b"))
+        );
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(":a".into())
+        );
+        assert_eq!(eval.next(), None);
+    }
+
+    #[test]
+    fn test_eval_applicative_order_diverges() {
+        let context = setup();
+
+        // ``k:a```sii``sii は正規順序なら停止するが、作用的順序では停止しない
+        let omega = expr::a(expr::a("s", "i"), "i");
+        let expr = expr::a(expr::a("k", ":a"), expr::a(omega.clone(), omega));
+
+        let eval = Eval::new(context.clone(), expr.clone(), Strategy::NormalOrder);
+        assert_eq!(
+            eval.last().map(|step| step.unwrap().expr),
+            Some(":a".into())
+        );
+
+        let mut eval = Eval::new(context, expr, Strategy::ApplicativeOrder).with_step_limit(100);
+        assert_eq!(eval.nth(100), Some(Err(Error::StepLimitExceeded(100))));
+    }
+
+    #[test]
+    fn test_eval_call_by_name() {
+        let context = setup();
+
+        // 先頭が簡約基でなければ引数の中は簡約しない
+        let expr = expr::a(":f", expr::a("i", ":a"));
+        let mut eval = Eval::new(context.clone(), expr, Strategy::CallByName);
+        assert_eq!(eval.next_path(), None);
+        assert_eq!(eval.next(), None);
+
+        // ``k`i:a`i:b => `i:a => :a
+        let expr = expr::a(expr::a("k", expr::a("i", ":a")), expr::a("i", ":b"));
+        let mut eval = Eval::new(context, expr, Strategy::CallByName);
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(expr::a("i", ":a"))
+        );
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(":a".into())
+        );
+        assert_eq!(eval.next(), None);
+    }
+
+    #[test]
+    fn test_eval_call_by_value() {
+        let context = setup();
+
+        // ``k:a`i:b => ``k:a:b => :a
+        let expr = expr::a(expr::a("k", ":a"), expr::a("i", ":b"));
+        let mut eval = Eval::new(context.clone(), expr, Strategy::CallByValue);
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(expr::a(expr::a("k", ":a"), ":b"))
+        );
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(":a".into())
+        );
+        assert_eq!(eval.next(), None);
+
+        // 引数は弱頭部正規形まで簡約されれば十分で、その内側までは簡約しない
+        // ``k`:f`i:a:b => `:f`i:a
+        let expr = expr::a(expr::a("k", expr::a(":f", expr::a("i", ":a"))), ":b");
+        let mut eval = Eval::new(context, expr, Strategy::CallByValue);
+        assert_eq!(
+            eval.next().map(|step| step.unwrap().expr),
+            Some(expr::a(":f", expr::a("i", ":a")))
+        );
+        assert_eq!(eval.next(), None);
+    }
+
+    /// キャッシュを使わずに木を辿り直して簡約基を含むかどうかを判定する
+    fn reducible_uncached(inventory: &Inventory) -> bool {
+        inventory.arity.is_some()
+            || inventory
+                .args
+                .enumerate()
+                .any(|(_, arg)| reducible_uncached(arg))
+    }
+
+    /// キャッシュされた reducible と引数の位置が、木を辿り直した結果と一致することを確かめる
+    fn assert_reducible_cache(inventory: &Inventory) {
+        assert_eq!(inventory.reducible, reducible_uncached(inventory));
+        assert_eq!(
+            inventory.reducible_arg,
+            inventory
+                .args
+                .enumerate()
+                .position(|(_, arg)| reducible_uncached(arg))
+        );
+        assert_eq!(
+            inventory.callable_arg,
+            inventory
+                .args
+                .enumerate()
+                .position(|(_, arg)| arg.arity.is_some())
+        );
+        inventory
+            .args
+            .enumerate()
+            .for_each(|(_, arg)| assert_reducible_cache(arg));
+    }
+
+    #[test]
+    fn test_eval_reducible_cache() {
+        let context = setup();
+
+        // ```s`k:a`i:b``k:c`i:d
+        let expr = expr::a(
+            expr::a(expr::a("s", expr::a("k", ":a")), expr::a("i", ":b")),
+            expr::a(expr::a("k", ":c"), expr::a("i", ":d")),
+        );
+
+        for strategy in [
+            Strategy::NormalOrder,
+            Strategy::ApplicativeOrder,
+            Strategy::CallByName,
+            Strategy::CallByValue,
+        ] {
+            let mut eval = Eval::new(context.clone(), expr.clone(), strategy);
+            assert_reducible_cache(&eval.inventory);
+            while let Some(step) = eval.next() {
+                step.unwrap();
+                assert_reducible_cache(&eval.inventory);
+            }
+        }
+    }
+
+    #[test]
+    fn test_eval_reducible_path() {
+        let context = setup();
+        let expr = expr::a(expr::a(expr::a("s", "i"), expr::a("k", ":b")), ":a");
+
+        let mut eval = Eval::new(context, expr, Strategy::NormalOrder);
+        assert_eq!(eval.reducible_path().map(Vec::<usize>::from), Some(vec![3]));
+
+        eval.next();
+        assert_eq!(eval.reducible_path().map(Vec::<usize>::from), Some(vec![1]));
+
+        eval.next();
+        assert_eq!(
+            eval.reducible_path().map(Vec::<usize>::from),
+            Some(vec![1, 2])
+        );
+
+        eval.next();
+        assert_eq!(eval.reducible_path().map(Vec::<usize>::from), None);
+    }
+
+    #[test]
+    fn test_eval_expr() {
+        let context = setup();
+        let expr = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
+
+        let mut eval = Eval::new(context, expr.clone(), Strategy::NormalOrder);
+        assert_eq!(eval.expr(), expr);
+        assert_eq!(eval.step(), 0);
+
+        let step = eval.next().unwrap().unwrap();
+        assert_eq!(eval.expr(), step.expr);
+        assert_eq!(eval.step(), 1);
+
+        eval.next();
+        assert_eq!(eval.expr(), expr::s("a"));
+        assert_eq!(eval.step(), 2);
+    }
+
+    #[test]
+    fn test_eval_cycle_detection() {
+        let context = setup();
+
+        // `^x.`xx^x.`xx => `^x.`xx^x.`xx => ...
+        let omega = expr::l("x", expr::a("x", "x"));
+        let expr = expr::a(omega.clone(), omega);
+        let mut eval =
+            Eval::new(context.clone(), expr.clone(), Strategy::NormalOrder).with_cycle_detection();
+
+        assert_eq!(eval.next().map(|step| step.unwrap().expr), Some(expr));
+        assert_eq!(
+            eval.next(),
+            Some(Err(Error::Diverged {
+                cycle_start: 0,
+                period: 1
+            }))
+        );
+        assert_eq!(eval.next(), None);
+
+        // ```s:a:b:c => ``:a:c`:b:c は停止するので検出されない
+        let expr = expr::a(expr::a(expr::a("s", ":a"), ":b"), ":c");
+        let eval = Eval::new(context, expr, Strategy::NormalOrder).with_cycle_detection();
+        assert_eq!(
+            eval.collect::<Result<Vec<_>, _>>().map(|steps| steps.len()),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn test_eval_cycle_detection_period() {
+        let context = setup();
+
+        // ``F:aF => `^y.``y:ayF => ``F:aF => ... (F = ^x.^y.``yxy) は 2 ステップごとに同じ式に戻る
+        let f = expr::l("x", expr::l("y", expr::a(expr::a("y", "x"), "y")));
+        let expr = expr::a(expr::a(f.clone(), ":a"), f);
+        let eval = Eval::new(context, expr, Strategy::NormalOrder)
+            .with_step_limit(100)
+            .with_cycle_detection();
+
+        let result = eval.filter_map(|step| step.err()).next();
+        assert_eq!(
+            result,
+            Some(Error::Diverged {
+                cycle_start: 0,
+                period: 2
+            })
+        );
+    }
+
+    #[test]
+    fn test_eval_eta_reduction() {
+        let context = setup();
+
+        // :g(x => :f(x)) は β 簡約できないが、η 簡約すると :g(:f) になる
+        let expr = expr::a(":g", expr::l("x", expr::a(":f", "x")));
+
+        let eval = Eval::new(context.clone(), expr.clone(), Strategy::NormalOrder);
+        assert_eq!(eval.next_path(), None);
+
+        let mut eval = Eval::new(context.clone(), expr, Strategy::NormalOrder).with_eta_reduction();
+        assert_eq!(eval.next_path().map(Vec::<usize>::from), Some(vec![1, 0]));
+
+        let step = eval.next().unwrap().unwrap();
+        assert_eq!(step.kind, StepKind::Eta);
+        assert_eq!(step.expr, expr::a(":g", ":f"));
+        assert_eq!(step.next_path, None);
+        assert_eq!(eval.next(), None);
+
+        // η 簡約の結果 β 簡約基が現れたら β 簡約を続ける
+        // x => i(:a)(x) => i(:a) => :a
+        let expr = expr::l("x", expr::a(expr::a("i", ":a"), "x"));
+        let eval = Eval::new(context, expr, Strategy::NormalOrder).with_eta_reduction();
+        let steps: Vec<EvalStep> = eval.map(|step| step.unwrap()).collect();
+
+        assert_eq!(
+            steps
+                .iter()
+                .map(|step| (step.kind, step.expr.clone()))
+                .collect::<Vec<_>>(),
+            vec![
+                (StepKind::Eta, expr::a("i", ":a")),
+                (StepKind::Beta, expr::s("a")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eval_eta_reduction_beta_first() {
+        let context = setup();
+
+        // β 簡約基が残っている間は β 簡約を優先する
+        let expr = expr::a(
+            expr::a("k", expr::l("x", expr::a(":f", "x"))),
+            expr::a("i", ":a"),
+        );
+        let eval = Eval::new(context, expr, Strategy::NormalOrder).with_eta_reduction();
+        let kinds: Vec<StepKind> = eval.map(|step| step.unwrap().kind).collect();
+
+        assert_eq!(kinds, vec![StepKind::Beta, StepKind::Eta]);
+    }
 }
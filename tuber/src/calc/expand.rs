@@ -1,30 +1,50 @@
 use crate::context::Context;
+use crate::error::Error;
 use crate::expr::{self, Expr, Identifier};
 use std::collections::HashSet;
 
-pub fn expand(context: &Context, expr: Expr) -> Expr {
+/// 式の中の自由変数を Context に定義済みの関数で置き換え、置き換えた関数の本体も再帰的に展開する
+///
+/// 自分自身を直接または間接に参照する関数は展開が停止しないので Error::RecursiveDefinition を返す
+pub fn expand(context: &Context, expr: Expr) -> Result<Expr, Error> {
     let mut bound_vars = BoundVars::new();
-    expand_(context, expr, &mut bound_vars)
+    expand_(context, expr, &mut bound_vars, &mut Vec::new())
 }
 
-fn expand_(context: &Context, expr: Expr, bound_vars: &mut BoundVars) -> Expr {
+/// expanding は展開している途中の関数の名前
+fn expand_(
+    context: &Context,
+    expr: Expr,
+    bound_vars: &mut BoundVars,
+    expanding: &mut Vec<Identifier>,
+) -> Result<Expr, Error> {
     match expr {
         Expr::Variable(ref id) if !bound_vars.contains(id) => match context.get(id) {
             Some(func) => {
+                if expanding.contains(id) {
+                    return Err(Error::RecursiveDefinition(id.clone()));
+                }
+
+                expanding.push(id.clone());
                 let mut bound_vars = BoundVars::new();
-                expand_(context, func.to_owned().into(), &mut bound_vars)
+                let expanded = expand_(context, func.to_owned().into(), &mut bound_vars, expanding);
+                expanding.pop();
+                expanded
             }
-            None => expr,
+            None => Ok(expr),
         },
-        Expr::Variable(_) => expr,
-        Expr::Symbol(_) => expr,
-        Expr::Apply { lhs, rhs } => expr::a(
-            expand_(context, *lhs, bound_vars),
-            expand_(context, *rhs, bound_vars),
-        ),
+        Expr::Variable(_) => Ok(expr),
+        Expr::Symbol(_) => Ok(expr),
+        Expr::Apply { lhs, rhs } => Ok(expr::a(
+            expand_(context, *lhs, bound_vars, expanding)?,
+            expand_(context, *rhs, bound_vars, expanding)?,
+        )),
         Expr::Lambda { param, body } => {
             bound_vars.insert(param.clone());
-            expr::l(param, expand_(context, *body, bound_vars))
+            Ok(expr::l(
+                param,
+                expand_(context, *body, bound_vars, expanding)?,
+            ))
         }
     }
 }
@@ -44,3 +64,40 @@ impl BoundVars {
         self.0.contains(id)
     }
 }
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::func;
+
+    #[test]
+    fn test_expand() {
+        let mut context = Context::new();
+        context.def(func::new("i", vec!["x"], "x"));
+        context.def(func::new("II", Vec::<&str>::new(), expr::a("i", "i")));
+
+        assert_eq!(
+            expand(&context, expr::a("II", ":a")),
+            Ok(expr::a(expr::a(expr::l("x", "x"), expr::l("x", "x")), ":a"))
+        );
+    }
+
+    #[test]
+    fn test_expand_recursive() {
+        let mut context = Context::new();
+        context.def(func::new("LOOP", vec!["x"], expr::a("LOOP", "x")));
+        context.def(func::new("EVEN", vec!["n"], expr::a("ODD", "n")));
+        context.def(func::new("ODD", vec!["n"], expr::a("EVEN", "n")));
+
+        assert_eq!(
+            expand(&context, expr::a("LOOP", ":a")),
+            Err(Error::RecursiveDefinition("LOOP".into()))
+        );
+        assert_eq!(
+            expand(&context, expr::v("EVEN")),
+            Err(Error::RecursiveDefinition("EVEN".into()))
+        );
+    }
+}
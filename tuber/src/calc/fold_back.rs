@@ -0,0 +1,298 @@
+use super::equivalence::normalize;
+use crate::context::Context;
+use crate::expr::{self, Expr, FreeVars, Identifier};
+use std::collections::HashMap;
+
+/// 関数の正規形を求めるときに簡約を打ち切るまでのステップ数
+const STEP_LIMIT: usize = 100;
+
+/// 式の中で Context に定義済みの関数と同じ項を、関数の名前に置き換える
+///
+/// 引数をとらない関数と、関数そのものの正規形にα同値な部分式は関数の名前に置き換える
+/// 引数をとる関数は、引数を与えた正規形をパターンとして部分式と照合し、関数適用の形に置き換える
+///
+/// 同じ正規形を持つ関数が複数ある場合は、引数をとらない関数、チャーチ数の順に優先し
+/// その中では名前の辞書順で最初の関数を用いる (0 と FALSE と NIL は 0 になる)
+/// ただし with_argument_preference() で指定した関数の引数では、指定した名前を優先する
+/// (既定では CONS の 2 番目の引数は NIL になる)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FoldBack {
+    /// 正規形の alpha_hash から、その正規形と関数の名前を引く
+    closed: HashMap<u64, Vec<(Expr, Identifier)>>,
+
+    /// 引数を穴として与えた正規形と、関数の名前と穴の一覧
+    patterns: Vec<(Expr, Identifier, Vec<Identifier>)>,
+
+    /// 関数の名前と引数の位置から、その引数で優先する名前を引く
+    argument_preferences: HashMap<(Identifier, usize), Identifier>,
+}
+
+impl FoldBack {
+    pub fn new(context: &Context) -> Self {
+        let mut funcs: Vec<_> = context.iter().map(|(_, func)| func).collect();
+        funcs.sort_by_key(|func| {
+            let is_numeral = func.name().chars().all(|c| c.is_ascii_digit());
+            (func.arity() > 0, !is_numeral, func.name().to_string())
+        });
+
+        let mut fold_back = Self::default();
+        for func in funcs {
+            let name = Identifier::from(func.name());
+
+            if let Ok(Some(normal @ Expr::Lambda { .. })) =
+                normalize(context, expr::v(name.clone()), STEP_LIMIT)
+            {
+                fold_back
+                    .closed
+                    .entry(normal.alpha_hash())
+                    .or_default()
+                    .push((normal, name.clone()));
+            }
+
+            if func.arity() == 0 {
+                continue;
+            }
+
+            // 識別子として構文解析できない名前にして、式の中の識別子と衝突しないようにする
+            let holes: Vec<Identifier> = (0..func.arity())
+                .map(|i| Identifier::from(format!("${}", i)))
+                .collect();
+            let applied = holes.iter().fold(expr::v(name.clone()), |acc, hole| {
+                expr::a(acc, expr::s(hole.clone()))
+            });
+
+            // 穴だけの式や穴を捨てる式をパターンにすると、照合できても元の関数適用の形に戻せない
+            if let Ok(Some(pattern @ Expr::Lambda { .. })) = normalize(context, applied, STEP_LIMIT)
+            {
+                if holes.iter().all(|hole| contains_symbol(&pattern, hole)) {
+                    fold_back.patterns.push((pattern, name, holes));
+                }
+            }
+        }
+
+        // リストの末尾は 0 や FALSE ではなく NIL と表示する
+        if context.get(&Identifier::from("NIL")).is_some() {
+            fold_back = fold_back.with_argument_preference("CONS", 1, "NIL");
+        }
+
+        fold_back
+    }
+
+    /// 関数 func の index 番目 (0 から数える) の引数では、同じ正規形を持つ関数のうち name を優先する
+    pub fn with_argument_preference(
+        mut self,
+        func: impl Into<Identifier>,
+        index: usize,
+        name: impl Into<Identifier>,
+    ) -> Self {
+        self.argument_preferences
+            .insert((func.into(), index), name.into());
+        self
+    }
+
+    pub fn fold(&self, expr: &Expr) -> Expr {
+        self.fold_preferring(expr, None)
+    }
+
+    /// preferred が expr と同じ正規形を持つ関数の名前であれば、その名前に置き換える
+    fn fold_preferring(&self, expr: &Expr, preferred: Option<&Identifier>) -> Expr {
+        if let Some(name) = self.find_closed(expr, preferred) {
+            return expr::v(name.clone());
+        }
+
+        for (pattern, name, holes) in &self.patterns {
+            let mut bindings = HashMap::new();
+            if matches(
+                pattern,
+                expr,
+                &mut Vec::new(),
+                &mut Vec::new(),
+                &mut bindings,
+            ) {
+                return holes.iter().enumerate().fold(
+                    expr::v(name.clone()),
+                    |acc, (index, hole)| {
+                        let preferred = self.argument_preferences.get(&(name.clone(), index));
+                        expr::a(acc, self.fold_preferring(&bindings[hole], preferred))
+                    },
+                );
+            }
+        }
+
+        match expr {
+            Expr::Apply { lhs, rhs } => expr::a(self.fold(lhs), self.fold(rhs)),
+            Expr::Lambda { param, body } => expr::l(param.clone(), self.fold(body)),
+            _ => expr.clone(),
+        }
+    }
+
+    fn find_closed(&self, expr: &Expr, preferred: Option<&Identifier>) -> Option<&Identifier> {
+        if !matches!(expr, Expr::Lambda { .. }) {
+            return None;
+        }
+
+        let mut names = self
+            .closed
+            .get(&expr.alpha_hash())?
+            .iter()
+            .filter(|(normal, _)| normal.alpha_eq(expr))
+            .map(|(_, name)| name);
+        let first = names.next()?;
+        Some(
+            preferred
+                .and_then(|preferred| names.find(|name| *name == preferred))
+                .unwrap_or(first),
+        )
+    }
+}
+
+/// パターンの穴以外の部分がα同値であれば、穴に対応する部分式を bindings に記録して true を返す
+///
+/// パターンの中で束縛されている変数を参照する部分式は穴に対応させない
+fn matches<'a>(
+    pattern: &'a Expr,
+    expr: &'a Expr,
+    pattern_bound_vars: &mut Vec<&'a Identifier>,
+    expr_bound_vars: &mut Vec<&'a Identifier>,
+    bindings: &mut HashMap<Identifier, Expr>,
+) -> bool {
+    match (pattern, expr) {
+        (Expr::Symbol(hole), _) if hole.as_str().starts_with('$') => {
+            let free_vars = FreeVars::from(expr);
+            if expr_bound_vars.iter().any(|id| free_vars.contains(id)) {
+                return false;
+            }
+
+            match bindings.get(hole) {
+                Some(bound) => bound.alpha_eq(expr),
+                None => {
+                    bindings.insert(hole.clone(), expr.clone());
+                    true
+                }
+            }
+        }
+
+        (Expr::Variable(p), Expr::Variable(e)) => {
+            let p = pattern_bound_vars.iter().rev().position(|id| *id == p);
+            let e = expr_bound_vars.iter().rev().position(|id| *id == e);
+            // パターンは閉じた式なので、パターンの変数は全て束縛変数
+            p.is_some() && p == e
+        }
+
+        (Expr::Symbol(p), Expr::Symbol(e)) => p == e,
+
+        (Expr::Apply { lhs: pl, rhs: pr }, Expr::Apply { lhs: el, rhs: er }) => {
+            matches(pl, el, pattern_bound_vars, expr_bound_vars, bindings)
+                && matches(pr, er, pattern_bound_vars, expr_bound_vars, bindings)
+        }
+
+        (
+            Expr::Lambda {
+                param: pp,
+                body: pb,
+            },
+            Expr::Lambda {
+                param: ep,
+                body: eb,
+            },
+        ) => {
+            pattern_bound_vars.push(pp);
+            expr_bound_vars.push(ep);
+            let result = matches(pb, eb, pattern_bound_vars, expr_bound_vars, bindings);
+            pattern_bound_vars.pop();
+            expr_bound_vars.pop();
+            result
+        }
+
+        _ => false,
+    }
+}
+
+fn contains_symbol(expr: &Expr, symbol: &Identifier) -> bool {
+    match expr {
+        Expr::Symbol(id) => id == symbol,
+        Expr::Variable(_) => false,
+        Expr::Apply { lhs, rhs } => contains_symbol(lhs, symbol) || contains_symbol(rhs, symbol),
+        Expr::Lambda { body, .. } => contains_symbol(body, symbol),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::func;
+
+    #[test]
+    fn test_fold_back_closed() {
+        let fold_back = FoldBack::new(&Context::default());
+
+        // x => y => x は TRUE と k の両方に一致するが、引数をとらない TRUE を優先する
+        let e = expr::l("x", expr::l("y", "x"));
+        assert_eq!(fold_back.fold(&e), expr::v("TRUE"));
+
+        // x => y => y は 0 と FALSE と NIL に一致するが、チャーチ数を優先する
+        let e = expr::l("x", expr::l("y", "y"));
+        assert_eq!(fold_back.fold(&e), expr::v("0"));
+
+        // チャーチ数
+        let e = expr::l(
+            "f",
+            expr::l("x", (0..7).fold(expr::v("x"), |acc, _| expr::a("f", acc))),
+        );
+        assert_eq!(fold_back.fold(&e), expr::v("7"));
+
+        // 部分式も置き換える
+        let e = expr::a(":a", expr::l("x", "x"));
+        assert_eq!(fold_back.fold(&e), expr::a(":a", "i"));
+
+        // 自由変数を含む式は置き換えない
+        let e = expr::l("x", "y");
+        assert_eq!(fold_back.fold(&e), e);
+    }
+
+    #[test]
+    fn test_fold_back_pattern() {
+        let fold_back = FoldBack::new(&Context::default());
+
+        // f => f(:a)(x => y => y) は CONS(:a, NIL)
+        let e = expr::l(
+            "f",
+            expr::a(expr::a("f", ":a"), expr::l("x", expr::l("y", "y"))),
+        );
+        assert_eq!(fold_back.fold(&e), expr::a(expr::a("CONS", ":a"), "NIL"));
+
+        // CONS の先頭の要素は 0 のまま
+        let e = expr::l(
+            "f",
+            expr::a(
+                expr::a("f", expr::l("x", expr::l("y", "y"))),
+                expr::l("x", expr::l("y", "y")),
+            ),
+        );
+        assert_eq!(fold_back.fold(&e), expr::a(expr::a("CONS", "0"), "NIL"));
+
+        // 優先する名前は変えられる
+        let fold_back = fold_back.with_argument_preference("CONS", 1, "FALSE");
+        let e = expr::l(
+            "f",
+            expr::a(expr::a("f", ":a"), expr::l("x", expr::l("y", "y"))),
+        );
+        assert_eq!(fold_back.fold(&e), expr::a(expr::a("CONS", ":a"), "FALSE"));
+
+        // パターンの中で束縛されている変数を参照する部分式は引数にならない
+        let e = expr::l("f", expr::a(expr::a("f", ":a"), "f"));
+        assert_eq!(fold_back.fold(&e), e);
+    }
+
+    #[test]
+    fn test_fold_back_priority() {
+        let a = func::new("A", Vec::<&str>::new(), expr::l("x", "x"));
+        let b = func::new("B", Vec::<&str>::new(), expr::l("y", "y"));
+        let i = func::new("i", vec!["x"], "x");
+        let fold_back = FoldBack::new(&Context::from(vec![i, b, a]));
+
+        assert_eq!(fold_back.fold(&expr::l("z", "z")), expr::v("A"));
+    }
+}
@@ -0,0 +1,520 @@
+use super::evaluate::{EvalStep, StepKind};
+use crate::context::Context;
+use crate::error::Error;
+use crate::expr::{self, Expr, FreeVars, Identifier, Path};
+use std::cmp;
+
+/// 必要呼び (call-by-need) でラムダ式を簡約する
+///
+/// 式をグラフとして保持し、関数に渡された引数は部分グラフとして共有される
+/// 簡約した簡約基はその場で簡約結果に書き換えられるため、同じ引数を二度簡約することはない
+/// 各ステップでは共有を展開した式のスナップショットを EvalStep として返す
+#[derive(Clone, Debug)]
+pub struct GraphEval {
+    context: Context,
+    heap: Heap,
+    root: NodeId,
+    next_redex: Option<Redex>,
+    step: usize,
+    step_limit: Option<usize>,
+    halted: bool,
+}
+
+impl GraphEval {
+    pub fn new(context: Context, expr: Expr) -> Self {
+        let mut heap = Heap::new();
+        let root = heap.build(&expr, &Vec::new());
+        let next_redex = heap.find_redex(&context, root);
+        Self {
+            context,
+            heap,
+            root,
+            next_redex,
+            step: 0,
+            step_limit: None,
+            halted: false,
+        }
+    }
+
+    /// 簡約のステップ数に上限を設ける
+    ///
+    /// 上限に達した後に簡約を進めようとすると Error::StepLimitExceeded を返す
+    pub fn with_step_limit(self, step_limit: usize) -> Self {
+        Self {
+            step_limit: Some(step_limit),
+            ..self
+        }
+    }
+
+    pub fn next_path(&self) -> Option<Path> {
+        self.next_redex.as_ref().map(|redex| redex.path.clone())
+    }
+
+    /// 現在の式を共有を展開した状態で得る
+    pub fn expr(&self) -> Expr {
+        self.heap.read_back(self.root)
+    }
+
+    /// スナップショットを作らずに簡約し尽くし、最終的な式を返す
+    pub fn normalize(&mut self) -> Result<Expr, Error> {
+        while !self.halted {
+            match self.reduce() {
+                Some(Ok(_)) => {}
+                Some(Err(err)) => return Err(err),
+                None => break,
+            }
+        }
+        Ok(self.expr())
+    }
+
+    /// 簡約を 1 ステップ進め、簡約した位置を返す
+    fn reduce(&mut self) -> Option<Result<Path, Error>> {
+        if self.halted {
+            return None;
+        }
+
+        let redex = self.next_redex.as_ref()?;
+
+        if let Some(step_limit) = self.step_limit {
+            if self.step >= step_limit {
+                self.halted = true;
+                return Some(Err(Error::StepLimitExceeded(step_limit)));
+            }
+        }
+
+        let mut reduced_path = redex.path.clone();
+
+        match self.heap.reduce(&self.context, redex) {
+            Ok(num_args) => {
+                reduced_path.set_arity(num_args);
+                self.next_redex = self.heap.find_redex(&self.context, self.root);
+                self.step += 1;
+                Some(Ok(reduced_path))
+            }
+            Err(err) => {
+                self.halted = true;
+                self.next_redex = None;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+impl Iterator for GraphEval {
+    type Item = Result<EvalStep, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.reduce()? {
+            Ok(reduced_path) => Some(Ok(EvalStep {
+                step: self.step,
+                expr: self.expr(),
+                reduced_path,
+                next_path: self.next_path(),
+                kind: StepKind::Beta,
+            })),
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+// ========================================================================== //
+
+type NodeId = usize;
+
+/// 束縛変数と、その変数に束縛された部分グラフの組
+///
+/// 後ろにある組ほど内側のスコープで束縛されている
+type Env = Vec<(Identifier, NodeId)>;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Node {
+    /// 変数
+    Variable(Identifier),
+
+    /// シンボル
+    Symbol(Identifier),
+
+    /// 適用
+    Apply(NodeId, NodeId),
+
+    /// ラムダ抽象
+    ///
+    /// ラムダ抽象の内側は簡約しないので、本体は式のまま環境と一緒に保持する
+    Closure {
+        param: Identifier,
+        body: Expr,
+        env: Env,
+    },
+
+    /// 簡約済みのノード、簡約結果のノードを指す
+    Indirection(NodeId),
+}
+
+/// 簡約基の情報
+#[derive(Clone, Debug, PartialEq)]
+struct Redex {
+    /// 簡約結果で書き換える適用のノード
+    node: NodeId,
+
+    /// 簡約基の先頭にある関数またはラムダ抽象のノード
+    callee: NodeId,
+
+    /// 関数に渡す引数のノード
+    args: Vec<NodeId>,
+
+    path: Path,
+}
+
+#[derive(Clone, Debug)]
+struct Heap {
+    nodes: Vec<Node>,
+
+    /// 内部に簡約基を持たないことが分かっているノード
+    normal: Vec<bool>,
+}
+
+impl Heap {
+    fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            normal: Vec::new(),
+        }
+    }
+
+    fn alloc(&mut self, node: Node) -> NodeId {
+        self.nodes.push(node);
+        self.normal.push(false);
+        self.nodes.len() - 1
+    }
+
+    /// 環境 env の下で式を部分グラフに変換する
+    ///
+    /// 環境で束縛されている変数は新たにノードを作らず、束縛された部分グラフを共有する
+    fn build(&mut self, expr: &Expr, env: &Env) -> NodeId {
+        match expr {
+            Expr::Variable(id) => match env.iter().rev().find(|(param, _)| param == id) {
+                Some((_, node)) => *node,
+                None => self.alloc(Node::Variable(id.clone())),
+            },
+            Expr::Symbol(id) => self.alloc(Node::Symbol(id.clone())),
+            Expr::Apply { lhs, rhs } => {
+                let lhs = self.build(lhs, env);
+                let rhs = self.build(rhs, env);
+                self.alloc(Node::Apply(lhs, rhs))
+            }
+            Expr::Lambda { param, body } => self.alloc(Node::Closure {
+                param: param.clone(),
+                body: body.as_ref().clone(),
+                env: env.clone(),
+            }),
+        }
+    }
+
+    /// 簡約済みのノードを辿って、実体のあるノードを得る
+    fn resolve(&self, mut id: NodeId) -> NodeId {
+        while let Node::Indirection(next) = self.nodes[id] {
+            id = next;
+        }
+        id
+    }
+
+    /// 適用の連なりを先頭の関数と引数に分解する
+    ///
+    /// 引数は適用のノードと引数のノードの組として、式に現れる順に返す
+    fn unwind(&self, id: NodeId) -> (NodeId, Vec<(NodeId, NodeId)>) {
+        let mut callee = self.resolve(id);
+        let mut spine = Vec::new();
+
+        while let Node::Apply(lhs, rhs) = self.nodes[callee] {
+            spine.push((callee, rhs));
+            callee = self.resolve(lhs);
+        }
+
+        spine.reverse();
+        (callee, spine)
+    }
+
+    fn arity(&self, context: &Context, id: NodeId) -> Option<usize> {
+        match &self.nodes[id] {
+            Node::Closure { .. } => Some(1),
+            Node::Variable(id) => context.get(id).map(|func| func.arity()),
+            _ => None,
+        }
+    }
+
+    /// 最左最外の簡約基を探す
+    fn find_redex(&mut self, context: &Context, id: NodeId) -> Option<Redex> {
+        let id = self.resolve(id);
+
+        if self.normal[id] {
+            return None;
+        }
+
+        let (callee, spine) = self.unwind(id);
+
+        if let Some(arity) = self
+            .arity(context, callee)
+            .filter(|arity| spine.len() >= cmp::max(1, *arity))
+        {
+            return Some(Redex {
+                node: spine[cmp::max(1, arity) - 1].0,
+                callee,
+                args: spine.iter().take(arity).map(|(_, arg)| *arg).collect(),
+                path: Path::Callee(arity),
+            });
+        }
+
+        for (index, (_, arg)) in spine.into_iter().enumerate() {
+            if let Some(mut redex) = self.find_redex(context, arg) {
+                redex.path = Path::Arg(index + 1, Box::new(redex.path));
+                return Some(redex);
+            }
+        }
+
+        self.normal[id] = true;
+        None
+    }
+
+    /// 簡約基を簡約し、簡約結果の引数の個数を返す
+    fn reduce(&mut self, context: &Context, redex: &Redex) -> Result<usize, Error> {
+        let result = match self.nodes[redex.callee].clone() {
+            Node::Closure {
+                param,
+                body,
+                mut env,
+            } => {
+                env.push((param, redex.args[0]));
+                self.build(&body, &env)
+            }
+            Node::Variable(id) => {
+                let func = context
+                    .get(&id)
+                    .ok_or_else(|| Error::UndefinedFunction(id.clone()))?;
+                let env = func.params().iter().cloned().zip(redex.args.clone());
+                self.build(func.body(), &env.collect())
+            }
+            _ => return Err(Error::NotAFunction(self.read_back(redex.callee))),
+        };
+
+        let num_args = self.unwind(result).1.len();
+
+        // arity が 0 の関数は引数を消費しないので、展開した本体に元の引数を適用し直す
+        // 関数名のノード自体を書き換えると、引数を伴わずに現れる箇所まで展開されてしまう
+        let result = match (redex.args.len(), &self.nodes[redex.node]) {
+            (0, Node::Apply(_, arg)) => {
+                let arg = *arg;
+                self.alloc(Node::Apply(result, arg))
+            }
+            _ => result,
+        };
+
+        // 簡約基をその場で書き換えることで、このノードを共有している全ての箇所に簡約結果が反映される
+        self.nodes[redex.node] = Node::Indirection(result);
+
+        Ok(num_args)
+    }
+
+    /// 共有を展開して式に戻す
+    fn read_back(&self, id: NodeId) -> Expr {
+        match &self.nodes[self.resolve(id)] {
+            Node::Variable(id) => Expr::Variable(id.clone()),
+            Node::Symbol(id) => Expr::Symbol(id.clone()),
+            Node::Apply(lhs, rhs) => expr::a(self.read_back(*lhs), self.read_back(*rhs)),
+            Node::Closure { param, body, env } => {
+                let mut expr = expr::l(param.clone(), body.clone());
+
+                // 環境の変数はすべて同時に置き換える必要がある
+                // 1 つずつ置き換えると、先に置き換えた値の自由変数が後の変数と取り違えられてしまうので、
+                // まず式に現れ得ない名前の仮の変数に置き換えてから値に置き換える
+                let mut placeholders = Vec::new();
+                for (index, (id, node)) in env.iter().enumerate() {
+                    // 内側のスコープで束縛し直されている変数は置き換えない
+                    let shadowed = env[index + 1..].iter().any(|(other, _)| other == id);
+                    if shadowed || !FreeVars::from(&expr).contains(id) {
+                        continue;
+                    }
+                    let placeholder = Identifier::from(format!("#{}", index));
+                    expr.substitute(id, &Expr::Variable(placeholder.clone()));
+                    placeholders.push((placeholder, *node));
+                }
+                for (placeholder, node) in placeholders {
+                    expr.substitute(&placeholder, &self.read_back(node));
+                }
+                expr
+            }
+            Node::Indirection(_) => unreachable!(),
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::{Eval, Strategy};
+    use crate::func;
+
+    fn setup() -> Context {
+        let i = func::new("i", vec!["x"], "x");
+        let k = func::new("k", vec!["x", "y"], "x");
+        let s = func::new(
+            "s",
+            vec!["x", "y", "z"],
+            expr::a(expr::a("x", "z"), expr::a("y", "z")),
+        );
+        let omega = func::new("OMEGA", Vec::<&str>::new(), expr::a(expr::a("s", "i"), "i"));
+
+        Context::from(vec![i, k, s, omega])
+    }
+
+    #[test]
+    fn test_graph_eval() {
+        let context = setup();
+
+        // ```skk:a => ``k:a`k:a => :a
+        let expr = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
+        let mut eval = GraphEval::new(context, expr);
+
+        assert_eq!(eval.next_path(), Some(Path::Callee(3)));
+
+        let step = eval.next().unwrap().unwrap();
+        assert_eq!(step.step, 1);
+        assert_eq!(step.expr, expr::a(expr::a("k", ":a"), expr::a("k", ":a")));
+        assert_eq!(step.reduced_path, Path::Callee(2));
+        assert_eq!(step.next_path, Some(Path::Callee(2)));
+
+        let step = eval.next().unwrap().unwrap();
+        assert_eq!(step.step, 2);
+        assert_eq!(step.expr, expr::s("a"));
+        assert_eq!(step.reduced_path, Path::Callee(0));
+        assert_eq!(step.next_path, None);
+
+        assert_eq!(eval.next(), None);
+    }
+
+    #[test]
+    fn test_graph_eval_path() {
+        let context = setup();
+
+        // `:f``k:a:b => `:f:a
+        let expr = expr::a(":f", expr::a(expr::a("k", ":a"), ":b"));
+        let mut eval = GraphEval::new(context, expr);
+
+        let expected = Path::Arg(1, Box::new(Path::Callee(2)));
+        assert_eq!(eval.next_path(), Some(expected));
+
+        let step = eval.next().unwrap().unwrap();
+        assert_eq!(step.expr, expr::a(":f", ":a"));
+        assert_eq!(step.reduced_path, Path::Arg(1, Box::new(Path::Callee(0))));
+        assert_eq!(eval.next(), None);
+    }
+
+    #[test]
+    fn test_graph_eval_sharing() {
+        let context = setup();
+
+        // `^x.``:fxx`i:a => ``:f`i:a`i:a => ``:f:a:a
+        // 共有された `i:a は一度しか簡約されない
+        let expr = expr::a(
+            expr::l("x", expr::a(expr::a(":f", "x"), "x")),
+            expr::a("i", ":a"),
+        );
+        let steps: Vec<EvalStep> = GraphEval::new(context, expr)
+            .map(|step| step.unwrap())
+            .collect();
+
+        assert_eq!(
+            steps
+                .iter()
+                .map(|step| step.expr.clone())
+                .collect::<Vec<_>>(),
+            vec![
+                expr::a(expr::a(":f", expr::a("i", ":a")), expr::a("i", ":a")),
+                expr::a(expr::a(":f", ":a"), ":a"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_graph_eval_closure() {
+        let context = setup();
+
+        // 簡約結果のラムダ抽象は環境を展開した形で読み戻される
+        // ``^x.^y.``:fxy:a => ^y.``:f:ay
+        let expr = expr::a(
+            expr::l("x", expr::l("y", expr::a(expr::a(":f", "x"), "y"))),
+            ":a",
+        );
+        let mut eval = GraphEval::new(context.clone(), expr);
+        assert_eq!(
+            eval.normalize(),
+            Ok(expr::l("y", expr::a(expr::a(":f", ":a"), "y")))
+        );
+
+        // 自由変数を捕獲しないようにリネームされる
+        // ``^x.^y.`xy y => ^Y.`yY
+        let expr = expr::a(expr::l("x", expr::l("y", expr::a("x", "y"))), "y");
+        let mut eval = GraphEval::new(context, expr);
+        assert_eq!(eval.normalize(), Ok(expr::l("Y", expr::a("y", "Y"))));
+    }
+
+    #[test]
+    fn test_graph_eval_closure_simultaneous_substitution() {
+        let context = setup();
+
+        // 環境の x に束縛された自由変数 y を、後から置き換える y と取り違えない
+        // ```^x.^y.^w.`xy y:b => ^w.`y:b
+        let expr = expr::a(
+            expr::a(
+                expr::l("x", expr::l("y", expr::l("w", expr::a("x", "y")))),
+                "y",
+            ),
+            ":b",
+        );
+
+        let last = Eval::new(context.clone(), expr.clone(), Strategy::NormalOrder)
+            .last()
+            .unwrap()
+            .unwrap();
+        assert_eq!(last.expr, expr::l("w", expr::a("y", ":b")));
+
+        let mut eval = GraphEval::new(context, expr);
+        assert_eq!(eval.normalize(), Ok(last.expr));
+    }
+
+    #[test]
+    fn test_graph_eval_church_numeral() {
+        let context = Context::default();
+
+        // ```POW 2 4:f:x
+        let expr = expr::a(expr::a(expr::a(expr::a("POW", "2"), "4"), ":f"), ":x");
+
+        let mut expected = expr::s("x");
+        for _ in 0..16 {
+            expected = expr::a(":f", expected);
+        }
+
+        let last = Eval::new(context.clone(), expr.clone(), Strategy::NormalOrder)
+            .last()
+            .unwrap()
+            .unwrap();
+        assert_eq!(last.expr, expected);
+
+        // 同じ正規形に、より少ないステップ数で到達する
+        let mut eval = GraphEval::new(context, expr);
+        assert_eq!(eval.normalize(), Ok(expected));
+        assert!(eval.step < last.step);
+    }
+
+    #[test]
+    fn test_graph_eval_step_limit() {
+        let context = setup();
+
+        let expr = expr::a("OMEGA", "OMEGA");
+        let mut eval = GraphEval::new(context, expr).with_step_limit(100);
+
+        assert_eq!(eval.normalize(), Err(Error::StepLimitExceeded(100)));
+        assert_eq!(eval.next(), None);
+    }
+}
@@ -0,0 +1,321 @@
+use super::evaluate::{Eval, EvalStep};
+use crate::error::Error;
+use crate::expr::{Expr, Path};
+
+/// チェックポイントを作る間隔の既定値
+pub const CHECKPOINT_INTERVAL: usize = 64;
+
+/// 簡約の履歴を保持し、前後のステップに移動できるようにする
+///
+/// 全てのステップの式を保持する代わりに、一定間隔ごとの式をチェックポイントとして保持する
+/// 過去のステップに戻るときは、直前のチェックポイントの式から Context と設定を共有した Eval を作り直して簡約をやり直す
+#[derive(Clone, Debug, PartialEq)]
+pub struct History {
+    /// checkpoints[i] は i * interval ステップ目の式
+    checkpoints: Vec<Expr>,
+    interval: usize,
+
+    /// これまでに到達した最大のステップまで簡約した Eval
+    frontier: Eval,
+
+    /// 過去のステップに戻ったときに、チェックポイントから簡約をやり直している Eval
+    replay: Option<Eval>,
+
+    /// frontier が簡約を進めようとして返したエラー
+    stopped: Option<Error>,
+
+    /// 現在の状態に至った簡約ステップ
+    current: Option<EvalStep>,
+
+    /// これまでに到達した最大のステップ数
+    known_steps: usize,
+
+    /// 簡約が最後まで進み、これ以上簡約できないことが分かっているかどうか
+    finished: bool,
+}
+
+impl History {
+    pub fn new(eval: Eval) -> Self {
+        Self {
+            checkpoints: vec![eval.expr()],
+            interval: CHECKPOINT_INTERVAL,
+            finished: eval.next_path().is_none(),
+            known_steps: eval.step(),
+            current: None,
+            stopped: None,
+            replay: None,
+            frontier: eval,
+        }
+    }
+
+    /// チェックポイントを作る間隔を指定する
+    pub fn with_interval(self, interval: usize) -> Self {
+        Self {
+            interval: interval.max(1),
+            ..self
+        }
+    }
+
+    /// 現在のステップ数
+    pub fn step(&self) -> usize {
+        self.eval().step()
+    }
+
+    /// これまでに到達した最大のステップ数
+    pub fn known_steps(&self) -> usize {
+        self.known_steps
+    }
+
+    /// 簡約が最後まで進んでいれば、簡約の総ステップ数を返す
+    pub fn total_steps(&self) -> Option<usize> {
+        self.finished.then_some(self.known_steps)
+    }
+
+    pub fn expr(&self) -> Expr {
+        self.eval().expr()
+    }
+
+    pub fn next_path(&self) -> Option<Path> {
+        self.eval().next_path()
+    }
+
+    pub fn reducible_path(&self) -> Option<Path> {
+        self.eval().reducible_path()
+    }
+
+    /// 現在の状態に至った簡約ステップを返す、0 ステップ目では None
+    pub fn current(&self) -> Option<&EvalStep> {
+        self.current.as_ref()
+    }
+
+    /// 1 ステップ前に戻り、移動した先のステップ数を返す
+    ///
+    /// 0 ステップ目にいる場合は移動しない
+    pub fn prev(&mut self) -> Result<usize, Error> {
+        let step = self.step();
+        self.seek(step.saturating_sub(1))
+    }
+
+    /// 指定したステップに移動し、移動した先のステップ数を返す
+    ///
+    /// 指定したステップに達する前に簡約が終わった場合は、最後のステップに移動する
+    pub fn seek(&mut self, step: usize) -> Result<usize, Error> {
+        if step < self.step() {
+            // step 0 以外は簡約ステップを得るため、1 ステップ手前以前のチェックポイントからやり直す
+            let index = step.saturating_sub(1) / self.interval;
+            let expr = self.checkpoints[index].clone();
+            self.replay = Some(self.frontier.resume(index * self.interval, expr));
+            self.current = None;
+        }
+
+        while self.step() < step {
+            match self.next() {
+                Some(result) => result?,
+                None => break,
+            };
+        }
+
+        Ok(self.step())
+    }
+
+    /// 現在の状態の Eval
+    fn eval(&self) -> &Eval {
+        self.replay.as_ref().unwrap_or(&self.frontier)
+    }
+}
+
+impl Iterator for History {
+    type Item = Result<EvalStep, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(replay) = &mut self.replay {
+            if replay.step() < self.known_steps {
+                let result = replay.next();
+                if let Some(Ok(step)) = &result {
+                    self.current = Some(step.clone());
+                }
+                return result;
+            }
+
+            // これまでに到達した最大のステップに追いついたので、以降は frontier で簡約を進める
+            self.replay = None;
+            if let Some(err) = &self.stopped {
+                return Some(Err(err.clone()));
+            }
+        }
+
+        let result = self.frontier.next();
+
+        match &result {
+            Some(Ok(step)) => {
+                self.current = Some(step.clone());
+                self.known_steps = step.step;
+                if step.step % self.interval == 0 {
+                    self.checkpoints.push(step.expr.clone());
+                }
+
+                if step.next_path.is_none() {
+                    self.finished = true;
+                }
+            }
+            // ステップ数の上限に達して止まった場合は、まだ簡約が終わったとは言えないので finished にはしない
+            Some(Err(err)) => self.stopped = Some(err.clone()),
+            None => {}
+        }
+
+        result
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::Strategy;
+    use crate::context::Context;
+    use crate::expr;
+    use crate::func;
+
+    fn setup() -> Context {
+        let i = func::new("i", vec!["x"], "x");
+        let k = func::new("k", vec!["x", "y"], "x");
+        let s = func::new(
+            "s",
+            vec!["x", "y", "z"],
+            expr::a(expr::a("x", "z"), expr::a("y", "z")),
+        );
+
+        Context::from(vec![i, k, s])
+    }
+
+    /// ```s`i:a`i:b:c => ```i:a:c``i:b:c => ``:a:c``i:b:c => ``:a:c`:b:c
+    fn history(interval: usize) -> History {
+        let expr = expr::a(
+            expr::a(expr::a("s", expr::a("i", ":a")), expr::a("i", ":b")),
+            ":c",
+        );
+        let eval = Eval::new(setup(), expr, Strategy::NormalOrder);
+        History::new(eval).with_interval(interval)
+    }
+
+    #[test]
+    fn test_history_next() {
+        let mut history = history(2);
+
+        assert_eq!(history.step(), 0);
+        assert_eq!(history.known_steps(), 0);
+        assert_eq!(history.total_steps(), None);
+        assert_eq!(history.current(), None);
+
+        let steps: Vec<EvalStep> = history.by_ref().map(|step| step.unwrap()).collect();
+        assert_eq!(steps.len(), 3);
+
+        assert_eq!(history.step(), 3);
+        assert_eq!(history.known_steps(), 3);
+        assert_eq!(history.total_steps(), Some(3));
+        assert_eq!(history.current(), steps.last());
+        assert_eq!(history.checkpoints.len(), 2);
+        assert_eq!(history.checkpoints[1], steps[1].expr);
+    }
+
+    #[test]
+    fn test_history_prev() {
+        let mut history = history(2);
+        let steps: Vec<EvalStep> = history.by_ref().map(|step| step.unwrap()).collect();
+
+        assert_eq!(history.prev(), Ok(2));
+        assert_eq!(history.current(), Some(&steps[1]));
+        assert_eq!(history.expr(), steps[1].expr);
+        assert_eq!(history.next_path(), steps[1].next_path);
+
+        assert_eq!(history.prev(), Ok(1));
+        assert_eq!(history.current(), Some(&steps[0]));
+
+        assert_eq!(history.prev(), Ok(0));
+        assert_eq!(history.current(), None);
+        assert_eq!(
+            history.expr(),
+            expr::a(
+                expr::a(expr::a("s", expr::a("i", ":a")), expr::a("i", ":b")),
+                ":c",
+            )
+        );
+
+        // 0 ステップ目より前には戻れない
+        assert_eq!(history.prev(), Ok(0));
+
+        // 戻った後も再び先に進める
+        assert_eq!(history.next(), Some(Ok(steps[0].clone())));
+        assert_eq!(history.known_steps(), 3);
+    }
+
+    #[test]
+    fn test_history_seek() {
+        let mut history = history(1);
+
+        // まだ到達していないステップにも移動できる
+        assert_eq!(history.seek(2), Ok(2));
+        assert_eq!(history.known_steps(), 2);
+        assert_eq!(history.total_steps(), None);
+        let step2 = history.current().cloned();
+
+        // 簡約が終わるステップを超えて移動しようとした場合は最後のステップに留まる
+        assert_eq!(history.seek(10), Ok(3));
+        assert_eq!(history.total_steps(), Some(3));
+        assert_eq!(
+            history.expr(),
+            expr::a(expr::a(":a", ":c"), expr::a(":b", ":c"))
+        );
+
+        assert_eq!(history.seek(2), Ok(2));
+        assert_eq!(history.current().cloned(), step2);
+
+        assert_eq!(history.seek(0), Ok(0));
+        assert_eq!(history.current(), None);
+    }
+
+    #[test]
+    fn test_history_diverged() {
+        // `^x.`xx^x.`xx は 1 ステップで元の式に戻る
+        let omega = expr::l("x", expr::a("x", "x"));
+        let eval = Eval::new(
+            setup(),
+            expr::a(omega.clone(), omega),
+            Strategy::NormalOrder,
+        )
+        .with_cycle_detection();
+        let mut history = History::new(eval).with_interval(1);
+
+        let diverged = Error::Diverged {
+            cycle_start: 0,
+            period: 1,
+        };
+        assert_eq!(history.seek(5), Err(diverged.clone()));
+        assert_eq!(history.step(), 1);
+
+        // チェックポイントからやり直しても、同じステップで同じエラーを返す
+        assert_eq!(history.seek(0), Ok(0));
+        assert_eq!(
+            history.next().map(|step| step.map(|step| step.step)),
+            Some(Ok(1))
+        );
+        assert_eq!(history.next(), Some(Err(diverged)));
+        assert_eq!(history.next(), None);
+    }
+
+    #[test]
+    fn test_history_step_limit() {
+        let omega = expr::a(expr::a("s", "i"), "i");
+        let expr = expr::a(omega.clone(), omega);
+        let eval = Eval::new(setup(), expr, Strategy::NormalOrder).with_step_limit(10);
+        let mut history = History::new(eval).with_interval(4);
+
+        assert_eq!(history.seek(20), Err(Error::StepLimitExceeded(10)));
+        assert_eq!(history.known_steps(), 10);
+
+        // 上限に達する前のステップには戻れる
+        assert_eq!(history.seek(5), Ok(5));
+        assert_eq!(history.current().map(|step| step.step), Some(5));
+    }
+}
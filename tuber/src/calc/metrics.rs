@@ -0,0 +1,110 @@
+use super::blc::blc_encode;
+use crate::context::Context;
+use crate::expr::{Expr, Identifier};
+
+/// 式の大きさを表す指標
+///
+/// ラムダ抽象を除去した結果を戦略ごとに比べるために使う
+#[derive(Clone, Debug, PartialEq)]
+pub struct Metrics {
+    /// 変数、シンボル、関数適用、ラムダ抽象の個数の合計
+    pub nodes: usize,
+
+    /// 根から最も深い葉までにあるノードの個数
+    pub depth: usize,
+
+    /// 自由変数として現れるコンビネータごとの出現回数
+    ///
+    /// 式の中で最初に現れた順に並べる
+    pub combinators: Vec<(Identifier, usize)>,
+
+    /// BLC で表現したときのビット長
+    ///
+    /// シンボルや未定義の自由変数を含む式は BLC で表現できないので None になる
+    pub blc_length: Option<usize>,
+}
+
+/// 式の大きさを測る
+pub fn metrics(context: &Context, expr: &Expr) -> Metrics {
+    let mut combinators = Vec::new();
+    count_combinators(expr, &mut Vec::new(), &mut combinators);
+
+    Metrics {
+        nodes: nodes(expr),
+        depth: depth(expr),
+        combinators,
+        blc_length: blc_encode(context, expr.clone())
+            .ok()
+            .map(|bits| bits.len()),
+    }
+}
+
+fn nodes(expr: &Expr) -> usize {
+    match expr {
+        Expr::Variable(_) | Expr::Symbol(_) => 1,
+        Expr::Apply { lhs, rhs } => 1 + nodes(lhs) + nodes(rhs),
+        Expr::Lambda { body, .. } => 1 + nodes(body),
+    }
+}
+
+fn depth(expr: &Expr) -> usize {
+    match expr {
+        Expr::Variable(_) | Expr::Symbol(_) => 1,
+        Expr::Apply { lhs, rhs } => 1 + depth(lhs).max(depth(rhs)),
+        Expr::Lambda { body, .. } => 1 + depth(body),
+    }
+}
+
+fn count_combinators<'a>(
+    expr: &'a Expr,
+    bound: &mut Vec<&'a Identifier>,
+    counts: &mut Vec<(Identifier, usize)>,
+) {
+    match expr {
+        Expr::Variable(id) if bound.contains(&id) => {}
+        Expr::Variable(id) => match counts.iter_mut().find(|(name, _)| *name == *id) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((id.clone(), 1)),
+        },
+        Expr::Symbol(_) => {}
+        Expr::Apply { lhs, rhs } => {
+            count_combinators(lhs, bound, counts);
+            count_combinators(rhs, bound, counts);
+        }
+        Expr::Lambda { param, body } => {
+            bound.push(param);
+            count_combinators(body, bound, counts);
+            bound.pop();
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_metrics() {
+        let context = Context::default();
+
+        // ``skk
+        let actual = metrics(&context, &expr::a(expr::a("s", "k"), "k"));
+        assert_eq!(actual.nodes, 5);
+        assert_eq!(actual.depth, 3);
+        assert_eq!(
+            actual.combinators,
+            vec![(Identifier::from("s"), 1), (Identifier::from("k"), 2)]
+        );
+        assert_eq!(actual.blc_length, Some(41));
+
+        // 束縛変数とシンボルはコンビネータとして数えない
+        let actual = metrics(&context, &expr::l("x", expr::a(expr::a("x", "i"), ":a")));
+        assert_eq!(actual.nodes, 6);
+        assert_eq!(actual.depth, 4);
+        assert_eq!(actual.combinators, vec![(Identifier::from("i"), 1)]);
+        assert_eq!(actual.blc_length, None);
+    }
+}
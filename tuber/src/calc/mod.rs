@@ -1,16 +1,32 @@
 mod apply;
 mod arity;
+mod blc;
+mod divergence;
+mod equivalence;
 mod evaluate;
 mod expand;
-mod reducer;
+mod fold_back;
+mod graph;
+mod history;
+mod metrics;
+mod strategy;
+mod typing;
 mod unlambda;
 
-pub use arity::arity;
-pub use evaluate::{Eval, EvalStep};
+pub use blc::blc_encode;
+pub use divergence::GrowthMonitor;
+pub use equivalence::{equivalence, Equivalence};
+pub use evaluate::{Eval, EvalStep, StepKind};
 pub use expand::expand;
-pub use reducer::{ReduceResult, Reducer};
+pub use fold_back::FoldBack;
+pub use graph::GraphEval;
+pub use history::History;
+pub use metrics::{metrics, Metrics};
+pub use strategy::Strategy;
+pub use typing::{infer_type, Type};
 pub use unlambda::{
-    unlambda_iota, unlambda_recursive, unlambda_recursive_, unlambda_recursive_sk,
-    unlambda_recursive_ski, unlambda_shallow, unlambda_shallow_, unlambda_shallow_sk,
-    unlambda_shallow_ski, RecursiveStrategy, ShallowStrategy,
+    kiselyov_bulk_combinators, unlambda_context, unlambda_iota, unlambda_recursive,
+    unlambda_recursive_, unlambda_recursive_sk, unlambda_recursive_ski, unlambda_shallow,
+    unlambda_shallow_, unlambda_shallow_sk, unlambda_shallow_ski, Basis, RecursiveStrategy,
+    ShallowStrategy,
 };
@@ -0,0 +1,29 @@
+/// 簡約基を選ぶ順序 (評価戦略) を表現する
+///
+/// いずれの戦略でもラムダ抽象の内側は簡約しない
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Strategy {
+    /// 正規順序
+    ///
+    /// 最も左にある最も外側の簡約基から簡約する (最左最外簡約)
+    #[default]
+    NormalOrder,
+
+    /// 作用的順序
+    ///
+    /// 最も左にある最も内側の簡約基から簡約する (最左最内簡約)
+    /// 関数に渡す引数はあらかじめ簡約し尽くされる
+    ApplicativeOrder,
+
+    /// 名前呼び
+    ///
+    /// 先頭の簡約基のみを簡約し、弱頭部正規形に達した時点で停止する
+    /// 引数は簡約されないまま関数に渡される
+    CallByName,
+
+    /// 値呼び
+    ///
+    /// 先頭の簡約基のみを簡約し、弱頭部正規形に達した時点で停止する
+    /// 関数に渡す引数はあらかじめ弱頭部正規形まで簡約される
+    CallByValue,
+}
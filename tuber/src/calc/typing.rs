@@ -0,0 +1,387 @@
+//! 単純型付きラムダ計算の型を Hindley–Milner の方式で推論する
+//!
+//! Context に定義済みの関数は、使われるたびに型変数を新しく取り直す (let 多相)
+//! 自分自身を直接または間接に参照する関数は、定義の中では単相的に扱う
+
+use crate::context::Context;
+use crate::error::Error;
+use crate::expr::{Expr, Identifier};
+use std::collections::HashMap;
+
+/// 型変数と関数型だけからなる型
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub enum Type {
+    /// 型変数
+    Var(usize),
+
+    /// 関数型 lhs → rhs
+    Arrow(Box<Type>, Box<Type>),
+}
+
+impl Type {
+    /// 関数型 lhs → rhs
+    pub fn arrow(lhs: Type, rhs: Type) -> Self {
+        Type::Arrow(Box::new(lhs), Box::new(rhs))
+    }
+
+    /// 型変数の番号を、現れた順に 0 から振り直す
+    pub fn normalize(&self) -> Self {
+        normalize(&[self]).remove(0)
+    }
+
+    fn occurs(&self, var: usize) -> bool {
+        match self {
+            Type::Var(v) => *v == var,
+            Type::Arrow(lhs, rhs) => lhs.occurs(var) || rhs.occurs(var),
+        }
+    }
+
+    fn vars(&self, vars: &mut Vec<usize>) {
+        match self {
+            Type::Var(v) if !vars.contains(v) => vars.push(*v),
+            Type::Var(_) => {}
+            Type::Arrow(lhs, rhs) => {
+                lhs.vars(vars);
+                rhs.vars(vars);
+            }
+        }
+    }
+
+    fn rename(&self, f: &impl Fn(usize) -> usize) -> Self {
+        match self {
+            Type::Var(v) => Type::Var(f(*v)),
+            Type::Arrow(lhs, rhs) => Type::arrow(lhs.rename(f), rhs.rename(f)),
+        }
+    }
+}
+
+/// 複数の型に共通する型変数の番号を、現れた順に 0 から振り直す
+fn normalize(types: &[&Type]) -> Vec<Type> {
+    let mut vars = Vec::new();
+    for t in types {
+        t.vars(&mut vars);
+    }
+    let index = |v: usize| vars.iter().position(|w| *w == v).unwrap();
+    types.iter().map(|t| t.rename(&index)).collect()
+}
+
+/// 式の主要型を推論する
+///
+/// 型を持たない式には Error::OccursCheck を返す
+/// Context に定義されていない自由変数はシンボルと同じく不透明な値として扱い、名前ごとに同じ型変数を与える
+pub fn infer_type(context: &Context, expr: &Expr) -> Result<Type, Error> {
+    let mut infer = Infer::new(context);
+    let t = infer.infer(&HashMap::new(), expr)?;
+    Ok(infer.resolve(&t).normalize())
+}
+
+// ========================================================================== //
+
+/// 型変数を量化した型
+#[derive(Clone, Debug)]
+struct Scheme {
+    generics: Vec<usize>,
+    t: Type,
+}
+
+struct Infer<'a> {
+    context: &'a Context,
+
+    /// 型変数に割り当てた型
+    substitution: Vec<Option<Type>>,
+
+    /// 型を推論し終えた関数
+    schemes: HashMap<Identifier, Scheme>,
+
+    /// 型を推論している途中の関数
+    pending: HashMap<Identifier, Type>,
+
+    /// シンボルの型
+    symbols: HashMap<Identifier, Type>,
+
+    /// Context に定義されていない自由変数の型
+    undefined: HashMap<Identifier, Type>,
+}
+
+impl<'a> Infer<'a> {
+    fn new(context: &'a Context) -> Self {
+        Self {
+            context,
+            substitution: Vec::new(),
+            schemes: HashMap::new(),
+            pending: HashMap::new(),
+            symbols: HashMap::new(),
+            undefined: HashMap::new(),
+        }
+    }
+
+    fn fresh(&mut self) -> Type {
+        self.substitution.push(None);
+        Type::Var(self.substitution.len() - 1)
+    }
+
+    fn infer(&mut self, env: &HashMap<&Identifier, Type>, expr: &Expr) -> Result<Type, Error> {
+        match expr {
+            Expr::Variable(id) => match env.get(id) {
+                Some(t) => Ok(t.clone()),
+                None => self.global(id),
+            },
+            Expr::Symbol(id) => match self.symbols.get(id) {
+                Some(t) => Ok(t.clone()),
+                None => {
+                    let t = self.fresh();
+                    self.symbols.insert(id.clone(), t.clone());
+                    Ok(t)
+                }
+            },
+            Expr::Apply { lhs, rhs } => {
+                let lhs = self.infer(env, lhs)?;
+                let rhs = self.infer(env, rhs)?;
+                let result = self.fresh();
+                self.unify(&lhs, &Type::arrow(rhs, result.clone()))?;
+                Ok(result)
+            }
+            Expr::Lambda { param, body } => {
+                let t = self.fresh();
+                let mut env = env.clone();
+                env.insert(param, t.clone());
+                let body = self.infer(&env, body)?;
+                Ok(Type::arrow(t, body))
+            }
+        }
+    }
+
+    /// Context に定義された関数の型、定義されていなければ不透明な値の型
+    fn global(&mut self, id: &Identifier) -> Result<Type, Error> {
+        if let Some(t) = self.pending.get(id) {
+            return Ok(t.clone());
+        }
+        if let Some(scheme) = self.schemes.get(id).cloned() {
+            return Ok(self.instantiate(&scheme));
+        }
+
+        if let Some(t) = self.undefined.get(id) {
+            return Ok(t.clone());
+        }
+
+        // 定義されていない自由変数はシンボルと同じく、名前ごとに同じ型変数を与える
+        let func = match self.context.get(id) {
+            Some(func) => func,
+            None => {
+                let t = self.fresh();
+                self.undefined.insert(id.clone(), t.clone());
+                return Ok(t);
+            }
+        };
+
+        let placeholder = self.fresh();
+        self.pending.insert(id.clone(), placeholder.clone());
+        let t = self.infer(&HashMap::new(), &Expr::from(func.clone()));
+        self.pending.remove(id);
+        let t = t?;
+        self.unify(&placeholder, &t)?;
+
+        let scheme = self.generalize(&t);
+        self.schemes.insert(id.clone(), scheme.clone());
+        Ok(self.instantiate(&scheme))
+    }
+
+    /// 推論している途中の関数の型に現れない型変数を量化する
+    fn generalize(&self, t: &Type) -> Scheme {
+        let t = self.resolve(t);
+
+        let mut monomorphic = Vec::new();
+        for pending in self.pending.values() {
+            self.resolve(pending).vars(&mut monomorphic);
+        }
+
+        let mut generics = Vec::new();
+        t.vars(&mut generics);
+        generics.retain(|v| !monomorphic.contains(v));
+
+        Scheme { generics, t }
+    }
+
+    fn instantiate(&mut self, scheme: &Scheme) -> Type {
+        let fresh: Vec<(usize, usize)> = scheme
+            .generics
+            .iter()
+            .map(|v| match self.fresh() {
+                Type::Var(w) => (*v, w),
+                _ => unreachable!(),
+            })
+            .collect();
+
+        self.resolve(&scheme.t).rename(&|v| {
+            fresh
+                .iter()
+                .find(|(generic, _)| *generic == v)
+                .map_or(v, |(_, w)| *w)
+        })
+    }
+
+    /// 型変数に割り当てた型をすべて展開する
+    fn resolve(&self, t: &Type) -> Type {
+        match t {
+            Type::Var(v) => match &self.substitution[*v] {
+                Some(t) => self.resolve(t),
+                None => t.clone(),
+            },
+            Type::Arrow(lhs, rhs) => Type::arrow(self.resolve(lhs), self.resolve(rhs)),
+        }
+    }
+
+    fn unify(&mut self, lhs: &Type, rhs: &Type) -> Result<(), Error> {
+        match (self.resolve(lhs), self.resolve(rhs)) {
+            (Type::Var(v), Type::Var(w)) if v == w => Ok(()),
+            (Type::Var(v), t) | (t, Type::Var(v)) => {
+                if t.occurs(v) {
+                    let types = normalize(&[&Type::Var(v), &t]);
+                    return Err(Error::OccursCheck {
+                        var: types[0].clone(),
+                        ty: types[1].clone(),
+                    });
+                }
+                self.substitution[v] = Some(t);
+                Ok(())
+            }
+            (Type::Arrow(l1, r1), Type::Arrow(l2, r2)) => {
+                self.unify(&l1, &l2)?;
+                self.unify(&r1, &r2)
+            }
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+    use crate::func;
+
+    fn v(n: usize) -> Type {
+        Type::Var(n)
+    }
+
+    #[test]
+    fn test_infer_type() {
+        let context = Context::default();
+        let infer = |e: Expr| infer_type(&context, &e);
+
+        // i : a → a
+        assert_eq!(infer(expr::v("i")), Ok(Type::arrow(v(0), v(0))));
+
+        // k : a → b → a
+        assert_eq!(
+            infer(expr::v("k")),
+            Ok(Type::arrow(v(0), Type::arrow(v(1), v(0))))
+        );
+
+        // s : (a → b → c) → (a → b) → a → c
+        assert_eq!(
+            infer(expr::v("s")),
+            Ok(Type::arrow(
+                Type::arrow(v(0), Type::arrow(v(1), v(2))),
+                Type::arrow(Type::arrow(v(0), v(1)), Type::arrow(v(0), v(2)))
+            ))
+        );
+
+        // ``skk : a → a
+        assert_eq!(
+            infer(expr::a(expr::a("s", "k"), "k")),
+            Ok(Type::arrow(v(0), v(0)))
+        );
+
+        // 定義済みの関数は使うたびに別の型変数を取る
+        // ``kii : a → a
+        assert_eq!(
+            infer(expr::a(expr::a("k", "i"), "i")),
+            Ok(Type::arrow(v(0), v(0)))
+        );
+
+        // CONS : a → b → (a → b → c) → c
+        assert_eq!(
+            infer(expr::v("CONS")),
+            Ok(Type::arrow(
+                v(0),
+                Type::arrow(
+                    v(1),
+                    Type::arrow(Type::arrow(v(0), Type::arrow(v(1), v(2))), v(2))
+                )
+            ))
+        );
+
+        // 同じシンボルは同じ型を持つ
+        // ``^x.^y.x:a:a : a
+        assert_eq!(
+            infer(expr::a(
+                expr::a(expr::l("x", expr::l("y", "x")), ":a"),
+                ":a"
+            )),
+            Ok(v(0))
+        );
+
+        // 定義されていない自由変数は不透明な値として扱う
+        // ^x.y : a → b
+        assert_eq!(infer(expr::l("x", "y")), Ok(Type::arrow(v(0), v(1))));
+
+        // 同じ名前の自由変数は同じ型を持つ
+        // ^f.``fyy : (a → a → b) → b
+        assert_eq!(
+            infer(expr::l("f", expr::a(expr::a("f", "y"), "y"))),
+            Ok(Type::arrow(
+                Type::arrow(v(0), Type::arrow(v(0), v(1))),
+                v(1)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_infer_type_error() {
+        let context = Context::default();
+
+        // ^x.`xx は型を持たない
+        assert_eq!(
+            infer_type(&context, &expr::l("x", expr::a("x", "x"))),
+            Err(Error::OccursCheck {
+                var: v(0),
+                ty: Type::arrow(v(0), v(1)),
+            })
+        );
+
+        assert_eq!(
+            infer_type(&context, &expr::v("Y")).map(|_| ()),
+            Err(Error::OccursCheck {
+                var: v(0),
+                ty: Type::arrow(v(0), v(1)),
+            })
+        );
+
+        // 未定義の自由変数は名前ごとに同じ型を持つので、自分自身に適用すると型を持たない
+        assert_eq!(
+            infer_type(&context, &expr::a("UNDEFINED", "UNDEFINED")).map(|_| ()),
+            Err(Error::OccursCheck {
+                var: v(0),
+                ty: Type::arrow(v(0), v(1)),
+            })
+        );
+    }
+
+    #[test]
+    fn test_infer_type_recursive() {
+        // ``LOOP x y = ``LOOP y x は x と y に同じ型を要求する
+        let mut context = Context::default();
+        context.def(func::new(
+            "LOOP",
+            vec!["x", "y"],
+            expr::a(expr::a("LOOP", "y"), "x"),
+        ));
+
+        assert_eq!(
+            infer_type(&context, &expr::v("LOOP")),
+            Ok(Type::arrow(v(0), Type::arrow(v(0), v(1))))
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use super::kiselyov::{self, Variant};
+use crate::context::Context;
+use crate::expr::{self, Expr, Identifier};
+use crate::func::{self, Func};
+
+/// ラムダ抽象を除去した結果を表すコンビネータの基底
+///
+/// 基底に含まれるコンビネータを Func として宣言し、S, K, I, B, C をそれらの組み合わせで表す
+/// 変換は S, K, I, B, C への変換を経由してから、それぞれを基底の式に置き換える
+#[derive(Clone, Debug, PartialEq)]
+pub struct Basis {
+    combinators: Vec<Func>,
+    s: Expr,
+    k: Expr,
+    i: Expr,
+    b: Expr,
+    c: Expr,
+}
+
+/// 基底の式に置き換える前の S, K, I, B, C の名前
+///
+/// 利用者が書いた変数と衝突しないよう、パーサーが受け付けない名前にしておく
+const PLACEHOLDERS: [&str; 5] = ["<s>", "<k>", "<i>", "<b>", "<c>"];
+
+impl Basis {
+    /// S, K, I をこの基底で表した式を与えて基底を作る
+    ///
+    /// B と C は S と K から組み立てる
+    /// B = ``S`KSK, C = ``S``BBS`KK
+    pub fn new(combinators: Vec<Func>, s: Expr, k: Expr, i: Expr) -> Self {
+        let b = expr::a(expr::a(s.clone(), expr::a(k.clone(), s.clone())), k.clone());
+        let c = expr::a(
+            expr::a(s.clone(), expr::a(expr::a(b.clone(), b.clone()), s.clone())),
+            expr::a(k.clone(), k.clone()),
+        );
+        Self {
+            combinators,
+            s,
+            k,
+            i,
+            b,
+            c,
+        }
+    }
+
+    /// B をこの基底で表した式を与える
+    pub fn with_b(self, b: Expr) -> Self {
+        Self { b, ..self }
+    }
+
+    /// C をこの基底で表した式を与える
+    pub fn with_c(self, c: Expr) -> Self {
+        Self { c, ..self }
+    }
+
+    /// S, K, I
+    pub fn ski() -> Self {
+        Self::new(
+            vec![s_func("s"), k_func("k"), i_func("i")],
+            expr::v("s"),
+            expr::v("k"),
+            expr::v("i"),
+        )
+    }
+
+    /// B, C, K, W
+    ///
+    /// S = ``B`BW``BBC, I = `WK
+    pub fn bckw() -> Self {
+        let combinators = vec![
+            func::new("b", vec!["x", "y", "z"], expr::a("x", expr::a("y", "z"))),
+            func::new("c", vec!["x", "y", "z"], expr::a(expr::a("x", "z"), "y")),
+            k_func("k"),
+            func::new("w", vec!["x", "y"], expr::a(expr::a("x", "y"), "y")),
+        ];
+        let s = expr::a(
+            expr::a("b", expr::a("b", "w")),
+            expr::a(expr::a("b", "b"), "c"),
+        );
+        Self::new(combinators, s, expr::v("k"), expr::a("w", "k"))
+            .with_b(expr::v("b"))
+            .with_c(expr::v("c"))
+    }
+
+    /// 1点基底 X = ^x.``xSK
+    ///
+    /// I = `XX, K = `X`X`XX, S = `X`X`X`XX
+    pub fn x() -> Self {
+        let s = expr::l(
+            "a",
+            expr::l(
+                "b",
+                expr::l("c", expr::a(expr::a("a", "c"), expr::a("b", "c"))),
+            ),
+        );
+        let k = expr::l("a", expr::l("b", "a"));
+        let body = expr::a(expr::a("x", s), k);
+        let x = || expr::v("X");
+        let i = expr::a(x(), x());
+        let k = expr::a(x(), expr::a(x(), i.clone()));
+        let s = expr::a(x(), k.clone());
+        Self::new(vec![func::new("X", vec!["x"], body)], s, k, i)
+    }
+
+    /// 基底に含まれるコンビネータ
+    pub fn combinators(&self) -> &Vec<Func> {
+        &self.combinators
+    }
+
+    /// 式からラムダ抽象を除去し、この基底のコンビネータだけで表す
+    pub fn unlambda(&self, context: &Context, expr: Expr) -> Expr {
+        let placeholders = PLACEHOLDERS.map(Identifier::from);
+        let expr = kiselyov::unlambda(context, expr, &placeholders.each_ref(), Variant::Eta);
+        self.translate(expr)
+    }
+
+    fn translate(&self, expr: Expr) -> Expr {
+        match expr {
+            Expr::Variable(ref id) => match PLACEHOLDERS.iter().position(|p| id.as_str() == *p) {
+                Some(0) => self.s.clone(),
+                Some(1) => self.k.clone(),
+                Some(2) => self.i.clone(),
+                Some(3) => self.b.clone(),
+                Some(4) => self.c.clone(),
+                _ => expr,
+            },
+            Expr::Apply { lhs, rhs } => expr::a(self.translate(*lhs), self.translate(*rhs)),
+            _ => expr,
+        }
+    }
+}
+
+fn s_func(name: &str) -> Func {
+    func::new(
+        name,
+        vec!["x", "y", "z"],
+        expr::a(expr::a("x", "z"), expr::a("y", "z")),
+    )
+}
+
+fn k_func(name: &str) -> Func {
+    func::new(name, vec!["x", "y"], "x")
+}
+
+fn i_func(name: &str) -> Func {
+    func::new(name, vec!["x"], "x")
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::equivalence::{equivalence, Equivalence};
+    use crate::expr::FreeVars;
+
+    /// 基底のコンビネータを定義した Context
+    fn setup(basis: &Basis) -> Context {
+        let mut context = Context::default();
+        for func in basis.combinators() {
+            context.def(func.clone());
+        }
+        context
+    }
+
+    /// 式が基底のコンビネータとシンボルだけでできているかどうか
+    fn consists_of(basis: &Basis, expr: &Expr) -> bool {
+        let names: Vec<&str> = basis.combinators().iter().map(|f| f.name()).collect();
+        FreeVars::from(expr).0.iter().all(|id| names.contains(id))
+    }
+
+    fn assert_round_trip(basis: &Basis, names: &[&str], step_limit: usize) {
+        let context = setup(basis);
+        for name in names {
+            let actual = basis.unlambda(&context, expr::v(*name));
+            assert!(consists_of(basis, &actual), "{}: {}", name, actual);
+            assert_eq!(
+                equivalence(&context, expr::v(*name), actual, step_limit),
+                Ok(Equivalence::Equal),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_ski() {
+        let basis = Basis::ski();
+        let context = setup(&basis);
+        assert_eq!(basis.unlambda(&context, expr::l("x", "x")), expr::v("i"));
+        assert_round_trip(
+            &basis,
+            &["TRUE", "NOT", "XOR", "SUCC", "ADD", "PRED"],
+            10000,
+        );
+    }
+
+    #[test]
+    fn test_bckw() {
+        let basis = Basis::bckw();
+        let context = setup(&basis);
+        assert_eq!(
+            basis.unlambda(&context, expr::l("x", "x")),
+            expr::a("w", "k")
+        );
+        assert_eq!(
+            basis.unlambda(&context, expr::l("x", expr::l("y", expr::a("y", "x")))),
+            expr::a("c", expr::a("w", "k"))
+        );
+        assert_round_trip(
+            &basis,
+            &["TRUE", "NOT", "XOR", "SUCC", "ADD", "PRED"],
+            10000,
+        );
+    }
+
+    #[test]
+    fn test_x() {
+        let basis = Basis::x();
+        assert_round_trip(&basis, &["TRUE", "FALSE", "NOT", "SUCC"], 100000);
+    }
+
+    #[test]
+    fn test_user_defined() {
+        // 大文字の名前で定義した S と K だけを使う基底
+        let basis = Basis::new(
+            vec![s_func("S"), k_func("K")],
+            expr::v("S"),
+            expr::v("K"),
+            expr::a(expr::a("S", "K"), "K"),
+        );
+        assert_round_trip(&basis, &["TRUE", "NOT", "SUCC"], 10000);
+    }
+}
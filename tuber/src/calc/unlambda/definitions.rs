@@ -0,0 +1,229 @@
+use super::{kiselyov_bulk_combinators, unlambda_recursive_, RecursiveStrategy};
+use crate::context::Context;
+use crate::expr::{Expr, FreeVars, Identifier};
+use crate::func::{self, Func};
+use std::collections::HashSet;
+
+/// Context に定義されたすべての関数からラムダ抽象を除去し、引数を取らないコンビネータの定義に書き換える
+///
+/// 他の関数への参照は展開してから変換する
+/// ただし、自分自身を直接または間接に参照する関数は展開が停止しないので、名前のまま残す
+/// 変換先のコンビネータ自身の定義は書き換えずにそのまま残し、KiselyovBulk が使う Bn, Cn, Sn の定義を加える
+pub fn unlambda_context(strategy: &RecursiveStrategy, context: &Context) -> Context {
+    let primitives = primitives(strategy);
+    let recursive = recursive_names(context);
+
+    // 展開してよい関数だけを残した Context
+    let mut inlinable = context.clone();
+    for id in primitives.iter().chain(recursive.iter()) {
+        inlinable.del(id);
+    }
+
+    let funcs = context
+        .iter()
+        .map(|(id, func)| {
+            if primitives.contains(id) {
+                func.clone()
+            } else {
+                let expr = unlambda_recursive_(strategy, &inlinable, func.clone().into());
+                func::new(id.clone(), Vec::<Identifier>::new(), expr)
+            }
+        })
+        .collect::<Vec<Func>>();
+
+    let mut result = Context::from(funcs.clone());
+
+    // KiselyovBulk の変換結果が使う Bn, Cn, Sn も定義に加える
+    if *strategy == RecursiveStrategy::KiselyovBulk {
+        for func in &funcs {
+            for bulk in kiselyov_bulk_combinators(func.body()) {
+                result.def(bulk);
+            }
+        }
+    }
+
+    result
+}
+
+/// 変換の結果に現れるコンビネータの名前
+fn primitives(strategy: &RecursiveStrategy) -> Vec<Identifier> {
+    let names = |names: &[&str]| names.iter().map(|&name| Identifier::from(name)).collect();
+
+    match strategy {
+        RecursiveStrategy::SKI => names(&["s", "k", "i"]),
+        RecursiveStrategy::SKIWith { s, k, i } => vec![s.clone(), k.clone(), i.clone()],
+        RecursiveStrategy::SK => names(&["s", "k"]),
+        RecursiveStrategy::SKWith { s, k } => vec![s.clone(), k.clone()],
+        RecursiveStrategy::Iota => names(&["ι"]),
+        RecursiveStrategy::IotaWith { iota } => vec![iota.clone()],
+        RecursiveStrategy::Kiselyov
+        | RecursiveStrategy::KiselyovEta
+        | RecursiveStrategy::KiselyovBulk => names(&["s", "k", "i", "b", "c"]),
+        RecursiveStrategy::Basis(basis) => basis
+            .combinators()
+            .iter()
+            .map(|func| Identifier::from(func.name()))
+            .collect(),
+        RecursiveStrategy::Turner => names(&["s", "k", "i", "b", "c", "s'", "b'", "c'"]),
+        RecursiveStrategy::TurnerWith {
+            s,
+            k,
+            i,
+            b,
+            c,
+            s2,
+            b2,
+            c2,
+        } => vec![s, k, i, b, c, s2, b2, c2]
+            .into_iter()
+            .cloned()
+            .collect(),
+    }
+}
+
+/// 自分自身を直接または間接に参照する関数の名前
+fn recursive_names(context: &Context) -> HashSet<Identifier> {
+    context
+        .iter()
+        .map(|(id, _)| id)
+        .filter(|id| reachable(context, id).contains(*id))
+        .cloned()
+        .collect()
+}
+
+/// id の定義から参照をたどって到達できる関数の名前
+fn reachable(context: &Context, id: &Identifier) -> HashSet<Identifier> {
+    let mut visited = HashSet::new();
+    let mut stack = vec![id.clone()];
+
+    while let Some(id) = stack.pop() {
+        let func = match context.get(&id) {
+            Some(func) => func,
+            None => continue,
+        };
+        let expr = Expr::from(func.clone());
+        for name in FreeVars::from(&expr).0 {
+            let name = Identifier::from(name);
+            if context.get(&name).is_some() && visited.insert(name.clone()) {
+                stack.push(name);
+            }
+        }
+    }
+
+    visited
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::equivalence::{equivalence, Equivalence};
+    use crate::calc::Basis;
+    use crate::expr;
+
+    #[test]
+    fn test_unlambda_context() {
+        let context = Context::default();
+        let actual = unlambda_context(&RecursiveStrategy::SKI, &context);
+
+        // 変換先のコンビネータはそのまま残す
+        assert_eq!(actual.get(&"s".into()), context.get(&"s".into()));
+
+        for (id, func) in actual.iter() {
+            if ["s", "k", "i"].contains(&id.as_str()) {
+                continue;
+            }
+            assert_eq!(func.arity(), 0, "{}", id);
+            assert!(
+                FreeVars::from(func.body())
+                    .0
+                    .iter()
+                    .all(|name| ["s", "k", "i"].contains(name)),
+                "{}: {}",
+                id,
+                func.body()
+            );
+        }
+
+        for name in ["TRUE", "NOT", "SUCC", "ADD", "PRED"] {
+            assert_eq!(
+                equivalence(
+                    &context,
+                    expr::v(name),
+                    actual.get(&name.into()).unwrap().body().clone(),
+                    10000
+                ),
+                Ok(Equivalence::Equal),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_unlambda_context_recursive() {
+        // ```LOOP x y = ```LOOP y x、`TWICE f = ``LOOP f f は展開が停止しない
+        let mut context = Context::default();
+        context.def(func::new(
+            "LOOP",
+            vec!["x", "y"],
+            expr::a(expr::a("LOOP", "y"), "x"),
+        ));
+        context.def(func::new(
+            "TWICE",
+            vec!["f"],
+            expr::a(expr::a("LOOP", "f"), "f"),
+        ));
+        context.def(func::new("EVEN", vec!["n"], expr::a("ODD", "n")));
+        context.def(func::new("ODD", vec!["n"], expr::a("EVEN", "n")));
+
+        let actual = unlambda_context(&RecursiveStrategy::SKI, &context);
+
+        // 自分自身を参照する関数は名前のまま残す
+        let body = |name: &str| actual.get(&name.into()).unwrap().body().clone();
+        assert_eq!(
+            body("LOOP"),
+            expr::a(expr::a("s", expr::a("k", expr::a("s", "LOOP"))), "k")
+        );
+        assert_eq!(body("TWICE"), expr::a(expr::a("s", "LOOP"), "i"));
+        assert_eq!(body("EVEN"), expr::v("ODD"));
+        assert_eq!(body("ODD"), expr::v("EVEN"));
+    }
+
+    #[test]
+    fn test_unlambda_context_bulk() {
+        let context = Context::default();
+        let actual = unlambda_context(&RecursiveStrategy::KiselyovBulk, &context);
+
+        // 変換結果に現れる名前はすべて定義されている
+        for (id, func) in actual.iter() {
+            let expr = Expr::from(func.clone());
+            for name in FreeVars::from(&expr).0 {
+                assert!(actual.get(&name.into()).is_some(), "{}: {}", id, name);
+            }
+        }
+
+        let body = actual.get(&"SUB".into()).unwrap().body().clone();
+        assert_eq!(
+            equivalence(&actual, expr::v("SUB"), body, 10000),
+            Ok(Equivalence::Equal)
+        );
+    }
+
+    #[test]
+    fn test_unlambda_context_basis() {
+        let basis = Basis::bckw();
+        let mut context = Context::default();
+        for func in basis.combinators() {
+            context.def(func.clone());
+        }
+
+        let actual = unlambda_context(&RecursiveStrategy::Basis(basis), &context);
+        let body = actual.get(&"FALSE".into()).unwrap().body().clone();
+        assert!(FreeVars::from(&body)
+            .0
+            .iter()
+            .all(|name| ["b", "c", "k", "w"].contains(name)));
+    }
+}
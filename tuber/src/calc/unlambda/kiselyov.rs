@@ -0,0 +1,470 @@
+use crate::context::Context;
+use crate::expr::{self, DeBruijnExpr, Expr, FreeVars, Identifier};
+use crate::func::{self, Func};
+use std::cmp;
+
+/// Kiselyov の "λ to SKI, Semantically" のうち、どの変換を使うか
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Variant {
+    /// S, K, I, B, C だけを使う素朴な変換
+    Plain,
+
+    /// 環境の最も内側の変数を必要とするかどうかを区別し、η 簡約できる部分を省く変換
+    Eta,
+
+    /// 一度に n 個の変数を受け渡す Bn, Cn, Sn を使い、変換後の大きさを線形に抑える変換
+    Bulk,
+}
+
+/// Kiselyov のアルゴリズムでラムダ抽象を除去する
+///
+/// ski は [s, k, i, b, c] の順に並べる
+/// Bulk では 2 個以上の変数を受け渡すコンビネータを B2, C3, S4 のような名前で表す
+pub fn unlambda(context: &Context, expr: Expr, ski: &[&Identifier; 5], variant: Variant) -> Expr {
+    let compiler = Compiler { context, ski };
+    let expr = DeBruijnExpr::from(expr);
+
+    match variant {
+        Variant::Plain => {
+            let (_, d) = compiler.plain(&expr);
+            d
+        }
+        Variant::Eta => match compiler.eta(&expr) {
+            Term::Closed(d) => d,
+            // 式の外側で束縛された変数は存在しないので、ここに来ることはない
+            term => panic!("unexpected free bound variable: {:?}", term),
+        },
+        Variant::Bulk => {
+            let (_, d) = compiler.bulk(&expr);
+            d
+        }
+    }
+}
+
+/// Bulk の変換結果に現れる Bn, Cn, Sn の定義を、名前の順に返す
+///
+/// - ``Bn f g x1 … xn = f (g x1 … xn)``
+/// - ``Cn f g x1 … xn = f x1 … xn g``
+/// - ``Sn f g x1 … xn = f x1 … xn (g x1 … xn)``
+pub fn bulk_combinators(expr: &Expr, ski: &[&Identifier; 5]) -> Vec<Func> {
+    let mut names: Vec<&str> = FreeVars::from(expr).0.into_iter().collect();
+    names.sort_unstable();
+
+    let kinds = [(ski[0], Bulk::S), (ski[3], Bulk::B), (ski[4], Bulk::C)];
+    names
+        .into_iter()
+        .filter_map(|name| {
+            kinds.iter().find_map(|(id, kind)| {
+                let n = name
+                    .strip_prefix(id.as_str().to_uppercase().as_str())?
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|n| *n >= 2)?;
+                Some(kind.definition(name, n))
+            })
+        })
+        .collect()
+}
+
+/// 一度に n 個の変数を受け渡すコンビネータの種類
+#[derive(Clone, Copy)]
+enum Bulk {
+    S,
+    B,
+    C,
+}
+
+impl Bulk {
+    fn definition(self, name: &str, n: usize) -> Func {
+        let xs: Vec<String> = (1..=n).map(|i| format!("X{}", i)).collect();
+        let apply = |f: &str| {
+            xs.iter()
+                .fold(expr::v(f), |f, x| expr::a(f, expr::v(x.as_str())))
+        };
+
+        let body = match self {
+            Bulk::S => expr::a(apply("F"), apply("G")),
+            Bulk::B => expr::a("F", apply("G")),
+            Bulk::C => expr::a(apply("F"), "G"),
+        };
+
+        let mut params = vec!["F".to_string(), "G".to_string()];
+        params.extend(xs);
+        func::new(name, params, body)
+    }
+}
+
+struct Compiler<'a> {
+    context: &'a Context,
+    ski: &'a [&'a Identifier; 5],
+}
+
+impl Compiler<'_> {
+    fn s(&self) -> Expr {
+        expr::v(self.ski[0].to_owned())
+    }
+
+    fn k(&self) -> Expr {
+        expr::v(self.ski[1].to_owned())
+    }
+
+    fn i(&self) -> Expr {
+        expr::v(self.ski[2].to_owned())
+    }
+
+    fn b(&self) -> Expr {
+        expr::v(self.ski[3].to_owned())
+    }
+
+    fn c(&self) -> Expr {
+        expr::v(self.ski[4].to_owned())
+    }
+
+    /// 自由変数を変換する
+    ///
+    /// Context に定義された関数は展開してから変換し、それ以外の変数はそのまま残す
+    fn free(&self, id: &Identifier, compile: impl Fn(&DeBruijnExpr) -> Expr) -> Expr {
+        if self.ski.contains(&id) {
+            return expr::v(id.to_owned());
+        }
+
+        match self.context.get(id) {
+            Some(func) => compile(&DeBruijnExpr::from(Expr::from(func.to_owned()))),
+            None => expr::v(id.to_owned()),
+        }
+    }
+
+    // ---------------------------------------------------------------------- //
+
+    /// 式を、環境のうち内側から n 個の変数を受け取るコンビネータ d の組 (n, d) に変換する
+    fn plain(&self, expr: &DeBruijnExpr) -> (usize, Expr) {
+        match expr {
+            DeBruijnExpr::Bound { index, .. } => {
+                let mut var = (1, self.i());
+                for _ in 0..*index {
+                    let n = var.0 + 1;
+                    var = (n, self.plain_apply((0, self.k()), var));
+                }
+                var
+            }
+            DeBruijnExpr::Free(id) => (0, self.free(id, |e| self.plain(e).1)),
+            DeBruijnExpr::Symbol(id) => (0, expr::s(id.to_owned())),
+            DeBruijnExpr::Apply { lhs, rhs } => {
+                let lhs = self.plain(lhs);
+                let rhs = self.plain(rhs);
+                (cmp::max(lhs.0, rhs.0), self.plain_apply(lhs, rhs))
+            }
+            DeBruijnExpr::Lambda { body, .. } => match self.plain(body) {
+                (0, d) => (0, expr::a(self.k(), d)),
+                (n, d) => (n - 1, d),
+            },
+        }
+    }
+
+    fn plain_apply(&self, lhs: (usize, Expr), rhs: (usize, Expr)) -> Expr {
+        match (lhs, rhs) {
+            ((0, d1), (0, d2)) => expr::a(d1, d2),
+            ((0, d1), (n, d2)) => self.plain_apply((0, expr::a(self.b(), d1)), (n - 1, d2)),
+            // R = CC
+            ((n, d1), (0, d2)) => {
+                let r = expr::a(expr::a(self.c(), self.c()), d2);
+                self.plain_apply((0, r), (n - 1, d1))
+            }
+            ((n1, d1), (n2, d2)) => {
+                let lhs = self.plain_apply((0, self.s()), (n1 - 1, d1));
+                self.plain_apply((n1 - 1, lhs), (n2 - 1, d2))
+            }
+        }
+    }
+
+    // ---------------------------------------------------------------------- //
+
+    fn eta(&self, expr: &DeBruijnExpr) -> Term {
+        match expr {
+            DeBruijnExpr::Bound { index, .. } => {
+                let mut var = Term::Need(Box::new(Term::Closed(self.i())));
+                for _ in 0..*index {
+                    var = Term::Weak(Box::new(var));
+                }
+                var
+            }
+            DeBruijnExpr::Free(id) => Term::Closed(self.free(id, |e| match self.eta(e) {
+                Term::Closed(d) => d,
+                term => panic!("unexpected free bound variable: {:?}", term),
+            })),
+            DeBruijnExpr::Symbol(id) => Term::Closed(expr::s(id.to_owned())),
+            DeBruijnExpr::Apply { lhs, rhs } => self.eta_apply(self.eta(lhs), self.eta(rhs)),
+            DeBruijnExpr::Lambda { body, .. } => match self.eta(body) {
+                Term::Closed(d) => Term::Closed(expr::a(self.k(), d)),
+                Term::Need(e) => *e,
+                Term::Weak(e) => self.eta_apply(Term::Closed(self.k()), *e),
+            },
+        }
+    }
+
+    fn eta_apply(&self, lhs: Term, rhs: Term) -> Term {
+        use Term::{Closed, Need, Weak};
+
+        match (lhs, rhs) {
+            (Closed(d1), Closed(d2)) => Closed(expr::a(d1, d2)),
+
+            // η 簡約: ^x.`Mx => M
+            (Closed(d1), Need(e2)) if *e2 == Closed(self.i()) => Need(Box::new(Closed(d1))),
+            (Weak(e1), Need(e2)) if *e2 == Closed(self.i()) => Need(e1),
+
+            (Closed(d1), Need(e2)) => {
+                Need(Box::new(self.eta_apply(Closed(expr::a(self.b(), d1)), *e2)))
+            }
+            (Closed(d1), Weak(e2)) => Weak(Box::new(self.eta_apply(Closed(d1), *e2))),
+            (Need(e1), Closed(d2)) => {
+                let e1 = self.eta_apply(Closed(self.c()), *e1);
+                Need(Box::new(self.eta_apply(e1, Closed(d2))))
+            }
+            (Need(e1), Need(e2)) => {
+                let e1 = self.eta_apply(Closed(self.s()), *e1);
+                Need(Box::new(self.eta_apply(e1, *e2)))
+            }
+            (Need(e1), Weak(e2)) => {
+                let e1 = self.eta_apply(Closed(self.c()), *e1);
+                Need(Box::new(self.eta_apply(e1, *e2)))
+            }
+            (Weak(e1), Closed(d2)) => Weak(Box::new(self.eta_apply(*e1, Closed(d2)))),
+            (Weak(e1), Need(e2)) => {
+                let e1 = self.eta_apply(Closed(self.b()), *e1);
+                Need(Box::new(self.eta_apply(e1, *e2)))
+            }
+            (Weak(e1), Weak(e2)) => Weak(Box::new(self.eta_apply(*e1, *e2))),
+        }
+    }
+
+    // ---------------------------------------------------------------------- //
+
+    fn bulk(&self, expr: &DeBruijnExpr) -> (usize, Expr) {
+        match expr {
+            DeBruijnExpr::Bound { index, .. } => {
+                let mut var = (1, self.i());
+                for _ in 0..*index {
+                    let n = var.0 + 1;
+                    var = (n, self.bulk_apply((0, self.k()), var));
+                }
+                var
+            }
+            DeBruijnExpr::Free(id) => (0, self.free(id, |e| self.bulk(e).1)),
+            DeBruijnExpr::Symbol(id) => (0, expr::s(id.to_owned())),
+            DeBruijnExpr::Apply { lhs, rhs } => {
+                let lhs = self.bulk(lhs);
+                let rhs = self.bulk(rhs);
+                (cmp::max(lhs.0, rhs.0), self.bulk_apply(lhs, rhs))
+            }
+            DeBruijnExpr::Lambda { body, .. } => match self.bulk(body) {
+                (0, d) => (0, expr::a(self.k(), d)),
+                (n, d) => (n - 1, d),
+            },
+        }
+    }
+
+    fn bulk_apply(&self, lhs: (usize, Expr), rhs: (usize, Expr)) -> Expr {
+        let b = |n| self.bulk_combinator(self.ski[3], n);
+        let c = |n| self.bulk_combinator(self.ski[4], n);
+        let s = |n| self.bulk_combinator(self.ski[0], n);
+
+        match (lhs, rhs) {
+            ((0, d1), (0, d2)) => expr::a(d1, d2),
+            // η 簡約: ^x.`Mx => M
+            ((0, d1), (1, d2)) if d2 == self.i() => d1,
+            ((0, d1), (n, d2)) => expr::a(expr::a(b(n), d1), d2),
+            ((n, d1), (0, d2)) => expr::a(expr::a(c(n), d1), d2),
+            ((n1, d1), (n2, d2)) if n1 == n2 => expr::a(expr::a(s(n1), d1), d2),
+            ((n1, d1), (n2, d2)) if n1 < n2 => expr::a(expr::a(b(n2 - n1), expr::a(s(n1), d1)), d2),
+            ((n1, d1), (n2, d2)) => {
+                let lhs = expr::a(expr::a(b(n1 - n2), s(n2)), d1);
+                expr::a(expr::a(c(n1 - n2), lhs), d2)
+            }
+        }
+    }
+
+    /// n 個の変数を受け渡すコンビネータ
+    ///
+    /// 1 個のときは元のコンビネータをそのまま使い、2 個以上のときは大文字の名前の後ろに n を付ける
+    fn bulk_combinator(&self, id: &Identifier, n: usize) -> Expr {
+        if n == 1 {
+            expr::v(id.to_owned())
+        } else {
+            expr::v(format!("{}{}", id.as_str().to_uppercase(), n))
+        }
+    }
+}
+
+/// η 最適化した変換の途中結果
+#[derive(Clone, Debug, PartialEq)]
+enum Term {
+    /// 環境の変数を1つも必要としないコンビネータ
+    Closed(Expr),
+
+    /// 環境の最も内側の変数を必要とする
+    Need(Box<Term>),
+
+    /// 環境の最も内側の変数を必要としない
+    Weak(Box<Term>),
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::equivalence::{equivalence, Equivalence};
+    use crate::calc::unlambda::unlambda_recursive;
+
+    fn ski() -> [Identifier; 5] {
+        ["s", "k", "i", "b", "c"].map(Identifier::from)
+    }
+
+    /// 式に含まれる変数とシンボルの個数
+    fn size(expr: &Expr) -> usize {
+        match expr {
+            Expr::Variable(_) | Expr::Symbol(_) => 1,
+            Expr::Apply { lhs, rhs } => size(lhs) + size(rhs),
+            Expr::Lambda { body, .. } => size(body),
+        }
+    }
+
+    /// 変換結果が使う Bn, Cn, Sn を context に定義する
+    fn with_bulk_combinators(context: &Context, expr: &Expr) -> Context {
+        let ski = ski();
+        let mut context = context.clone();
+        for func in bulk_combinators(expr, &ski.each_ref()) {
+            context.def(func);
+        }
+        context
+    }
+
+    #[test]
+    fn test_unlambda() {
+        let context = Context::default();
+        let ski = ski();
+        let ski: [&Identifier; 5] = ski.iter().collect::<Vec<_>>().try_into().unwrap();
+        let unlambda = |e: Expr, variant| unlambda(&context, e, &ski, variant);
+
+        // ^x.x == i
+        let source = expr::l("x", "x");
+        assert_eq!(unlambda(source.clone(), Variant::Plain), expr::v("i"));
+        assert_eq!(unlambda(source.clone(), Variant::Eta), expr::v("i"));
+        assert_eq!(unlambda(source, Variant::Bulk), expr::v("i"));
+
+        // ^x.^y.x == ``bki (Plain), k (Eta, Bulk)
+        let source = expr::l("x", expr::l("y", "x"));
+        let expected = expr::a(expr::a("b", "k"), "i");
+        assert_eq!(unlambda(source.clone(), Variant::Plain), expected);
+        assert_eq!(unlambda(source.clone(), Variant::Eta), expr::v("k"));
+        assert_eq!(unlambda(source, Variant::Bulk), expr::v("k"));
+
+        // ^x.^y.y == `ki
+        let source = expr::l("x", expr::l("y", "y"));
+        let expected = expr::a("k", "i");
+        assert_eq!(unlambda(source.clone(), Variant::Plain), expected);
+        assert_eq!(unlambda(source.clone(), Variant::Eta), expected);
+        assert_eq!(unlambda(source, Variant::Bulk), expected);
+
+        // ^f.^x.`fx == i (Eta)
+        let source = expr::l("f", expr::l("x", expr::a("f", "x")));
+        assert_eq!(unlambda(source, Variant::Eta), expr::v("i"));
+
+        // ^x.`:ax == :a (Eta)
+        let source = expr::l("x", expr::a(":a", "x"));
+        assert_eq!(unlambda(source, Variant::Eta), expr::s("a"));
+
+        // ^x.^y.^z.``xz`yz (Bulk)
+        let source = expr::l(
+            "x",
+            expr::l(
+                "y",
+                expr::l("z", expr::a(expr::a("x", "z"), expr::a("y", "z"))),
+            ),
+        );
+        let actual = unlambda(source.clone(), Variant::Bulk);
+        assert_eq!(
+            equivalence(
+                &with_bulk_combinators(&context, &actual),
+                source,
+                actual,
+                1000
+            ),
+            Ok(Equivalence::Equal)
+        );
+    }
+
+    #[test]
+    fn test_unlambda_equivalence() {
+        let context = Context::default();
+        let ski = ski();
+        let ski: [&Identifier; 5] = ski.iter().collect::<Vec<_>>().try_into().unwrap();
+
+        for name in ["NOT", "XOR", "SUCC", "ADD", "MUL", "PRED", "SUB"] {
+            let source = unlambda_recursive(&context, expr::v(name));
+            for variant in [Variant::Plain, Variant::Eta, Variant::Bulk] {
+                let actual = unlambda(&context, expr::v(name), &ski, variant);
+                assert_eq!(
+                    equivalence(
+                        &with_bulk_combinators(&context, &actual),
+                        source.clone(),
+                        actual,
+                        10000
+                    ),
+                    Ok(Equivalence::Equal),
+                    "{} {:?}",
+                    name,
+                    variant
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_bulk_combinators() {
+        let ski = ski();
+
+        // ``B2``C2S2kx
+        let expr = expr::a(expr::a("B2", expr::a(expr::a("C2", "S2"), "k")), "x");
+        let funcs = bulk_combinators(&expr, &ski.each_ref());
+        assert_eq!(
+            funcs,
+            vec![
+                func::new(
+                    "B2",
+                    vec!["F", "G", "X1", "X2"],
+                    expr::a("F", expr::a(expr::a("G", "X1"), "X2"))
+                ),
+                func::new(
+                    "C2",
+                    vec!["F", "G", "X1", "X2"],
+                    expr::a(expr::a(expr::a("F", "X1"), "X2"), "G")
+                ),
+                func::new(
+                    "S2",
+                    vec!["F", "G", "X1", "X2"],
+                    expr::a(
+                        expr::a(expr::a("F", "X1"), "X2"),
+                        expr::a(expr::a("G", "X1"), "X2")
+                    )
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unlambda_size() {
+        let context = Context::default();
+        let ski = ski();
+        let ski: [&Identifier; 5] = ski.iter().collect::<Vec<_>>().try_into().unwrap();
+
+        for name in ["Y", "DIV"] {
+            let plain = unlambda(&context, expr::v(name), &ski, Variant::Plain);
+            let eta = unlambda(&context, expr::v(name), &ski, Variant::Eta);
+            let bulk = unlambda(&context, expr::v(name), &ski, Variant::Bulk);
+
+            assert!(size(&eta) < size(&plain), "{}", name);
+            assert!(size(&bulk) < size(&plain), "{}", name);
+        }
+    }
+}
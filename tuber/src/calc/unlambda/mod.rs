@@ -1,14 +1,24 @@
+mod basis;
+mod definitions;
+mod kiselyov;
+#[cfg(test)]
+mod property;
 mod recursive_iota;
 mod recursive_sk;
 mod recursive_ski;
+mod recursive_turner;
 mod shallow_sk;
 mod shallow_ski;
 
 use crate::context::Context;
 use crate::expr::{Expr, Identifier};
+use crate::func::Func;
+pub use basis::Basis;
+pub use definitions::unlambda_context;
 pub use recursive_iota::unlambda as unlambda_recursive_iota;
 pub use recursive_sk::unlambda as unlambda_recursive_sk;
 pub use recursive_ski::unlambda as unlambda_recursive_ski;
+pub use recursive_turner::unlambda as unlambda_recursive_turner;
 pub use shallow_sk::unlambda as unlambda_shallow_sk;
 pub use shallow_ski::unlambda as unlambda_shallow_ski;
 
@@ -42,6 +52,17 @@ pub fn unlambda_iota(context: &Context, expr: Expr) -> Expr {
     unlambda_recursive_iota(context, expr, &("ι".into()))
 }
 
+fn unlambda_kiselyov(context: &Context, expr: Expr, variant: kiselyov::Variant) -> Expr {
+    let ski = ["s", "k", "i", "b", "c"].map(Identifier::from);
+    kiselyov::unlambda(context, expr, &ski.each_ref(), variant)
+}
+
+/// KiselyovBulk の変換結果が使う Bn, Cn, Sn の定義
+pub fn kiselyov_bulk_combinators(expr: &Expr) -> Vec<Func> {
+    let ski = ["s", "k", "i", "b", "c"].map(Identifier::from);
+    kiselyov::bulk_combinators(expr, &ski.each_ref())
+}
+
 pub fn unlambda_recursive_(strategy: &RecursiveStrategy, context: &Context, expr: Expr) -> Expr {
     match strategy {
         RecursiveStrategy::SKI => {
@@ -56,6 +77,26 @@ pub fn unlambda_recursive_(strategy: &RecursiveStrategy, context: &Context, expr
         RecursiveStrategy::SKWith { s, k } => unlambda_recursive_sk(context, expr, &[s, k]),
         RecursiveStrategy::Iota => unlambda_recursive_iota(context, expr, &("ι".into())),
         RecursiveStrategy::IotaWith { iota } => unlambda_recursive_iota(context, expr, iota),
+        RecursiveStrategy::Kiselyov => unlambda_kiselyov(context, expr, kiselyov::Variant::Plain),
+        RecursiveStrategy::KiselyovEta => unlambda_kiselyov(context, expr, kiselyov::Variant::Eta),
+        RecursiveStrategy::KiselyovBulk => {
+            unlambda_kiselyov(context, expr, kiselyov::Variant::Bulk)
+        }
+        RecursiveStrategy::Basis(basis) => basis.unlambda(context, expr),
+        RecursiveStrategy::Turner => {
+            let ids = ["s", "k", "i", "b", "c", "s'", "b'", "c'"].map(Identifier::from);
+            unlambda_recursive_turner(context, expr, &ids.each_ref())
+        }
+        RecursiveStrategy::TurnerWith {
+            s,
+            k,
+            i,
+            b,
+            c,
+            s2,
+            b2,
+            c2,
+        } => unlambda_recursive_turner(context, expr, &[s, k, i, b, c, s2, b2, c2]),
     }
 }
 
@@ -95,4 +136,24 @@ pub enum RecursiveStrategy {
     IotaWith {
         iota: Identifier,
     },
+    /// Turner の B, C, S', B', C' を使う
+    Turner,
+    /// 任意の基底のコンビネータを使う
+    Basis(Basis),
+    /// Kiselyov のアルゴリズムで S, K, I, B, C を使う
+    Kiselyov,
+    /// Kiselyov のアルゴリズムに η 簡約を組み込む
+    KiselyovEta,
+    /// Kiselyov のアルゴリズムで Bn, Cn, Sn を使い、変換後の大きさを線形に抑える
+    KiselyovBulk,
+    TurnerWith {
+        s: Identifier,
+        k: Identifier,
+        i: Identifier,
+        b: Identifier,
+        c: Identifier,
+        s2: Identifier,
+        b2: Identifier,
+        c2: Identifier,
+    },
 }
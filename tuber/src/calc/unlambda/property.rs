@@ -0,0 +1,351 @@
+//! ラムダ抽象の除去が式の意味を変えないことを、ランダムに生成した閉じたラムダ式で確かめる
+//!
+//! 元の式と変換した式のそれぞれに新しいシンボルを与えて正規形まで簡約し、η 正規化した結果がα同値になるかを調べる
+//! 反例が見つかった場合は、反例であり続ける範囲で式を小さくしてから報告する
+
+use super::{
+    kiselyov_bulk_combinators, unlambda_recursive_, unlambda_shallow_, Basis, RecursiveStrategy,
+    ShallowStrategy,
+};
+use crate::calc::equivalence::normalize_with_steps;
+use crate::context::Context;
+use crate::expr::{self, Expr, FreeVars, Identifier};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// 生成する式の大きさ (変数とシンボルの個数) の上限
+const MAX_SIZE: usize = 12;
+
+/// 変換した式に許すステップ数の、元の式のステップ数に対する倍率
+const TRANSLATED_STEP_FACTOR: usize = 1000;
+
+/// 変換した式に許すステップ数の、元の式のステップ数によらない余裕
+const TRANSLATED_STEP_MARGIN: usize = 5000;
+
+/// ランダムな閉じたラムダ式を生成する
+fn closed_term(rng: &mut StdRng, size: usize) -> Expr {
+    let mut scope = Vec::new();
+    term(rng, &mut scope, size)
+}
+
+fn term(rng: &mut StdRng, scope: &mut Vec<Identifier>, size: usize) -> Expr {
+    if size <= 1 {
+        return match scope.len() {
+            0 if rng.gen_bool(0.2) => expr::s("a"),
+            0 => expr::l("V0", "V0"),
+            n if rng.gen_bool(0.1) => expr::s(["a", "b"][n % 2]),
+            n => expr::v(scope[rng.gen_range(0..n)].clone()),
+        };
+    }
+
+    if scope.is_empty() || rng.gen_bool(0.3) {
+        let param = Identifier::from(format!("V{}", scope.len()));
+        scope.push(param.clone());
+        let body = term(rng, scope, size - 1);
+        scope.pop();
+        expr::l(param, body)
+    } else {
+        let lhs_size = rng.gen_range(1..size);
+        let lhs = term(rng, scope, lhs_size);
+        let rhs = term(rng, scope, size - lhs_size);
+        expr::a(lhs, rhs)
+    }
+}
+
+/// 式の大きさ
+fn size(expr: &Expr) -> usize {
+    match expr {
+        Expr::Variable(_) | Expr::Symbol(_) => 1,
+        Expr::Apply { lhs, rhs } => size(lhs) + size(rhs),
+        Expr::Lambda { body, .. } => 1 + size(body),
+    }
+}
+
+/// 式を1か所だけ小さくした閉じた式を列挙する
+fn shrink_candidates(expr: &Expr) -> Vec<Expr> {
+    let mut candidates = smaller(expr);
+
+    // 変数や束縛を取り除いた結果、閉じていない式になった候補は捨てる
+    candidates.retain(|candidate| FreeVars::from(candidate).0.is_empty());
+    candidates
+}
+
+/// 式を1か所だけ小さくした候補を列挙する
+fn smaller(expr: &Expr) -> Vec<Expr> {
+    let mut candidates = Vec::new();
+    match expr {
+        Expr::Symbol(_) => {}
+        // 部分式をシンボルに置き換えると、束縛変数の参照を減らしたまま閉じた式に保てる
+        Expr::Variable(_) => candidates.push(expr::s("a")),
+        Expr::Apply { lhs, rhs } => {
+            candidates.push(expr::s("a"));
+            candidates.push(lhs.as_ref().clone());
+            candidates.push(rhs.as_ref().clone());
+            for lhs in smaller(lhs) {
+                candidates.push(expr::a(lhs, rhs.as_ref().clone()));
+            }
+            for rhs in smaller(rhs) {
+                candidates.push(expr::a(lhs.as_ref().clone(), rhs));
+            }
+        }
+        Expr::Lambda { param, body } => {
+            candidates.push(expr::s("a"));
+            candidates.push(body.as_ref().clone());
+            for body in smaller(body) {
+                candidates.push(expr::l(param.clone(), body));
+            }
+        }
+    }
+    candidates
+}
+
+/// 反例であり続ける範囲で、できるだけ小さな式にする
+fn shrink(mut expr: Expr, fails: impl Fn(&Expr) -> bool) -> Expr {
+    while let Some(smaller) = shrink_candidates(&expr)
+        .into_iter()
+        .find(|candidate| fails(candidate))
+    {
+        expr = smaller;
+    }
+    expr
+}
+
+/// 式の先頭にあるラムダ抽象の個数
+fn leading_lambdas(expr: &Expr) -> usize {
+    match expr {
+        Expr::Lambda { body, .. } => 1 + leading_lambdas(body),
+        _ => 0,
+    }
+}
+
+// ========================================================================== //
+
+/// 変換の前後で式の意味が変わらないことを確かめる
+struct Harness {
+    context: Context,
+    cases: usize,
+    step_limit: usize,
+}
+
+/// 元の式と変換した式の正規形が食い違った例
+#[derive(Debug)]
+struct Counterexample {
+    source: Expr,
+    translated: Expr,
+    expected: Expr,
+    actual: Option<Expr>,
+}
+
+impl Harness {
+    fn new() -> Self {
+        let mut context = Context::default();
+        for basis in [Basis::bckw(), Basis::x()] {
+            for func in basis.combinators() {
+                context.def(func.clone());
+            }
+        }
+
+        Self {
+            context,
+            cases: 100,
+            step_limit: 2000,
+        }
+    }
+
+    /// 試す式の個数を変える
+    fn with_cases(self, cases: usize) -> Self {
+        Self { cases, ..self }
+    }
+
+    /// source と変換した式のそれぞれに新しいシンボルを与えて正規形まで簡約し、食い違えば反例を返す
+    ///
+    /// 元の式が上限までに正規形を持たない場合は判定できないので None を返す
+    /// 変換した式は、元の式が要したステップ数に応じた上限までに正規形に達しなければ反例とする
+    fn counterexample(
+        &self,
+        source: &Expr,
+        translate: &impl Fn(&Context, Expr) -> Expr,
+    ) -> Option<Counterexample> {
+        let translated = translate(&self.context, source.clone());
+
+        // KiselyovBulk の変換結果が使う Bn, Cn, Sn を定義する
+        let mut context = self.context.clone();
+        for func in kiselyov_bulk_combinators(&translated) {
+            context.def(func);
+        }
+
+        let apply = |expr: &Expr| {
+            (0..leading_lambdas(source)).fold(expr.clone(), |acc, n| {
+                expr::a(acc, expr::s(format!("ARG{}", n)))
+            })
+        };
+        let normalize = |expr: Expr, step_limit: usize| {
+            normalize_with_steps(&context, expr, step_limit)
+                .ok()
+                .flatten()
+                .map(|(expr, steps)| (expr.eta_normalize(), steps))
+        };
+
+        let (expected, steps) = normalize(apply(source), self.step_limit)?;
+        let actual =
+            normalize(apply(&translated), translated_step_limit(steps)).map(|(actual, _)| actual);
+
+        match &actual {
+            Some(actual) if actual.alpha_eq(&expected) => None,
+            _ => Some(Counterexample {
+                source: source.clone(),
+                translated,
+                expected,
+                actual,
+            }),
+        }
+    }
+
+    /// ランダムな式で変換を試し、反例が見つかれば小さくしてから返す
+    fn check(
+        &self,
+        seed: u64,
+        translate: impl Fn(&Context, Expr) -> Expr,
+    ) -> Option<Counterexample> {
+        let mut rng = StdRng::seed_from_u64(seed);
+
+        for _ in 0..self.cases {
+            let size = rng.gen_range(1..=MAX_SIZE);
+            let source = closed_term(&mut rng, size);
+
+            if self.counterexample(&source, &translate).is_some() {
+                let source = shrink(source, |candidate| {
+                    self.counterexample(candidate, &translate).is_some()
+                });
+                return self.counterexample(&source, &translate);
+            }
+        }
+
+        None
+    }
+}
+
+/// 元の式が steps ステップで正規形に達したときに、変換した式に許すステップ数
+///
+/// 変換した式はコンビネータを 1 つずつ簡約するので、元の式より多くのステップを要する
+/// X のような基底では数百倍のステップを要することがあるので、余裕を持たせる
+fn translated_step_limit(steps: usize) -> usize {
+    TRANSLATED_STEP_FACTOR * steps + TRANSLATED_STEP_MARGIN
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    /// 簡約の途中で式が深く入れ子になると既定のスタックでは足りないので、大きなスタックを持つスレッドで調べる
+    const STACK_SIZE: usize = 64 << 20;
+
+    fn assert_preserves(
+        name: &str,
+        cases: usize,
+        translate: impl Fn(&Context, Expr) -> Expr + Sync,
+    ) {
+        let seed = rand::thread_rng().gen();
+        let counterexample = thread::scope(|scope| {
+            thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn_scoped(scope, || {
+                    Harness::new().with_cases(cases).check(seed, &translate)
+                })
+                .unwrap()
+                .join()
+                .unwrap()
+        });
+
+        if let Some(counterexample) = counterexample {
+            panic!(
+                "{} (seed: {}): {} => {}\nexpected: {}\nactual: {:?}",
+                name,
+                seed,
+                counterexample.source,
+                counterexample.translated,
+                counterexample.expected,
+                counterexample.actual.map(|expr| expr.to_string()),
+            );
+        }
+    }
+
+    #[test]
+    fn test_recursive_strategies() {
+        let strategies = [
+            ("SKI", RecursiveStrategy::SKI, 100),
+            ("SK", RecursiveStrategy::SK, 100),
+            ("Iota", RecursiveStrategy::Iota, 50),
+            ("Turner", RecursiveStrategy::Turner, 100),
+            ("Kiselyov", RecursiveStrategy::Kiselyov, 100),
+            ("KiselyovEta", RecursiveStrategy::KiselyovEta, 100),
+            ("KiselyovBulk", RecursiveStrategy::KiselyovBulk, 100),
+            ("BCKW", RecursiveStrategy::Basis(Basis::bckw()), 100),
+            // X だけで表した式は簡約に多くのステップを要するので、試す式を減らす
+            ("X", RecursiveStrategy::Basis(Basis::x()), 20),
+        ];
+
+        for (name, strategy, cases) in strategies {
+            assert_preserves(name, cases, |context, expr| {
+                unlambda_recursive_(&strategy, context, expr)
+            });
+        }
+    }
+
+    #[test]
+    fn test_shallow_strategies() {
+        let strategies = [("SKI", ShallowStrategy::SKI), ("SK", ShallowStrategy::SK)];
+
+        for (name, strategy) in strategies {
+            assert_preserves(name, 100, |_context, expr| {
+                unlambda_shallow_(&strategy, expr)
+            });
+        }
+    }
+
+    #[test]
+    fn test_shrink() {
+        let harness = Harness::new();
+
+        // 常に i を返す誤った変換は、大きさ 1 の反例 :a まで小さくなる
+        let counterexample = harness
+            .check(0, |_context, _expr| expr::v("i"))
+            .expect("counterexample");
+        assert_eq!(counterexample.source, expr::s("a"));
+        assert_eq!(counterexample.translated, expr::v("i"));
+
+        // 内側のラムダ抽象の引数を入れ替えてしまう誤った変換
+        let swap = |context: &Context, expr: Expr| {
+            let expr = match expr {
+                Expr::Lambda { param, body } => match *body {
+                    Expr::Lambda { param: inner, body } => expr::l(inner, expr::l(param, *body)),
+                    body => expr::l(param, body),
+                },
+                expr => expr,
+            };
+            unlambda_recursive_(&RecursiveStrategy::SKI, context, expr)
+        };
+        let counterexample = harness.check(0, swap).expect("counterexample");
+        assert!(
+            size(&counterexample.source) <= 4,
+            "{}",
+            counterexample.source
+        );
+    }
+
+    #[test]
+    fn test_diverging_translation() {
+        let harness = Harness::new();
+
+        // 正規形を持たない式に変換してしまう誤った変換は反例になる
+        let omega = expr::l("x", expr::a("x", "x"));
+        let counterexample = harness
+            .check(0, |_context, _expr| expr::a(omega.clone(), omega.clone()))
+            .expect("counterexample");
+        assert_eq!(counterexample.source, expr::s("a"));
+        assert_eq!(counterexample.actual, None);
+    }
+}
@@ -0,0 +1,202 @@
+use crate::context::Context;
+use crate::expr::FreeVars;
+use crate::expr::{self, Expr, Identifier};
+
+/// Turner の B, C, S', B', C' を使ってラムダ抽象を除去する
+///
+/// ids は [s, k, i, b, c, s', b', c'] の順に並べる
+pub fn unlambda(context: &Context, expr: Expr, ids: &[&Identifier; 8]) -> Expr {
+    match expr {
+        Expr::Variable(ref id) => {
+            if ids.contains(&id) {
+                expr
+            } else {
+                match context.get(id) {
+                    Some(func) => unlambda(context, func.to_owned().into(), ids),
+                    None => expr,
+                }
+            }
+        }
+        Expr::Symbol(_) => expr,
+        Expr::Apply { lhs, rhs } => {
+            expr::a(unlambda(context, *lhs, ids), unlambda(context, *rhs, ids))
+        }
+        Expr::Lambda { param, body } => unlambda(context, unlambda_(*body, &param, ids), ids),
+    }
+}
+
+fn unlambda_(expr: Expr, param: &Identifier, ids: &[&Identifier; 8]) -> Expr {
+    let k: &Identifier = ids[1];
+    let i: &Identifier = ids[2];
+
+    match expr {
+        Expr::Variable(id) if &id == param => expr::v(i.to_owned()),
+        Expr::Variable(_) => expr::a(expr::v(k.to_owned()), expr),
+        Expr::Symbol(_) => expr::a(expr::v(k.to_owned()), expr),
+        Expr::Apply { .. } if !FreeVars::from(&expr).contains(param) => {
+            expr::a(expr::v(k.to_owned()), expr)
+        }
+        Expr::Apply { lhs, rhs } => combine(
+            unlambda_(*lhs, param, ids),
+            unlambda_(*rhs, param, ids),
+            ids,
+        ),
+        Expr::Lambda { param: inner, body } => unlambda_(unlambda_(*body, &inner, ids), param, ids),
+    }
+}
+
+/// S [x]E1 [x]E2 を Turner の規則に従って最適化する
+fn combine(lhs: Expr, rhs: Expr, ids: &[&Identifier; 8]) -> Expr {
+    // S (K p) (K q) => K (p q)
+    // S (K p) I     => p
+    // S (K (p q)) r => B' p q r
+    // S (K p) q     => B p q
+    // S (B p q) (K r) => C' p q r
+    // S p (K q)     => C p q
+    // S (B p q) r   => S' p q r
+    let [s, k, i, b, c, s2, b2, c2] = ids.map(|id| expr::v(id.to_owned()));
+
+    match (unapply_k(&lhs, &k), unapply_b(&lhs, &b)) {
+        (Some(p), _) => match unapply_k(&rhs, &k) {
+            Some(q) => expr::a(k, expr::a(p.clone(), q.clone())),
+            None if rhs == i => p.clone(),
+            None => match p {
+                Expr::Apply { lhs: p, rhs: q } => {
+                    expr::a(expr::a(expr::a(b2, *p.clone()), *q.clone()), rhs)
+                }
+                _ => expr::a(expr::a(b, p.clone()), rhs),
+            },
+        },
+        (None, Some((p, q))) => match unapply_k(&rhs, &k) {
+            Some(r) => expr::a(expr::a(expr::a(c2, p.clone()), q.clone()), r.clone()),
+            None => expr::a(expr::a(expr::a(s2, p.clone()), q.clone()), rhs),
+        },
+        (None, None) => match unapply_k(&rhs, &k) {
+            Some(q) => expr::a(expr::a(c, lhs), q.clone()),
+            None => expr::a(expr::a(s, lhs), rhs),
+        },
+    }
+}
+
+/// K p を p に分解する
+fn unapply_k<'a>(expr: &'a Expr, k: &Expr) -> Option<&'a Expr> {
+    match expr {
+        Expr::Apply { lhs, rhs } if lhs.as_ref() == k => Some(rhs),
+        _ => None,
+    }
+}
+
+/// B p q を p と q に分解する
+fn unapply_b<'a>(expr: &'a Expr, b: &Expr) -> Option<(&'a Expr, &'a Expr)> {
+    match expr {
+        Expr::Apply { lhs, rhs } => match lhs.as_ref() {
+            Expr::Apply {
+                lhs: callee,
+                rhs: p,
+            } if callee.as_ref() == b => Some((p, rhs)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::equivalence::{equivalence, Equivalence};
+    use crate::calc::unlambda::unlambda_recursive;
+
+    fn ids() -> [Identifier; 8] {
+        ["s", "k", "i", "b", "c", "s'", "b'", "c'"].map(Identifier::from)
+    }
+
+    /// 式に含まれる変数とシンボルの個数
+    fn size(expr: &Expr) -> usize {
+        match expr {
+            Expr::Variable(_) | Expr::Symbol(_) => 1,
+            Expr::Apply { lhs, rhs } => size(lhs) + size(rhs),
+            Expr::Lambda { body, .. } => size(body),
+        }
+    }
+
+    #[test]
+    fn test_unlambda() {
+        let context = Context::default();
+        let ids = ids();
+        let ids: [&Identifier; 8] = ids.iter().collect::<Vec<_>>().try_into().unwrap();
+
+        // ^x.x == i
+        let source = expr::l("x", "x");
+        assert_eq!(unlambda(&context, source, &ids), expr::v("i"));
+
+        // ^x.`yx == y
+        let source = expr::l("x", expr::a("y", "x"));
+        assert_eq!(unlambda(&context, source, &ids), expr::v("y"));
+
+        // ^x.`y`zx == ``byz
+        let source = expr::l("x", expr::a("y", expr::a("z", "x")));
+        let expected = expr::a(expr::a("b", "y"), "z");
+        assert_eq!(unlambda(&context, source, &ids), expected);
+
+        // ^x.``yz`wx == ```b'yzw
+        let source = expr::l("x", expr::a(expr::a("y", "z"), expr::a("w", "x")));
+        let expected = expr::a(expr::a(expr::a("b'", "y"), "z"), "w");
+        assert_eq!(unlambda(&context, source, &ids), expected);
+
+        // ^x.``yxz == ``cyz
+        let source = expr::l("x", expr::a(expr::a("y", "x"), "z"));
+        let expected = expr::a(expr::a("c", "y"), "z");
+        assert_eq!(unlambda(&context, source, &ids), expected);
+
+        // ^x.``y`zx`wx == ```s'yzw
+        let source = expr::l(
+            "x",
+            expr::a(expr::a("y", expr::a("z", "x")), expr::a("w", "x")),
+        );
+        let expected = expr::a(expr::a(expr::a("s'", "y"), "z"), "w");
+        assert_eq!(unlambda(&context, source, &ids), expected);
+
+        // ^x.``y`zxw == ```c'yzw
+        let source = expr::l("x", expr::a(expr::a("y", expr::a("z", "x")), "w"));
+        let expected = expr::a(expr::a(expr::a("c'", "y"), "z"), "w");
+        assert_eq!(unlambda(&context, source, &ids), expected);
+
+        // ^x.^y.`yx == `ci
+        let source = expr::l("x", expr::l("y", expr::a("y", "x")));
+        let expected = expr::a("c", "i");
+        assert_eq!(unlambda(&context, source, &ids), expected);
+
+        // FALSE => ^x.^y.y => `ki
+        assert_eq!(
+            unlambda(&context, expr::v("FALSE"), &ids),
+            expr::a("k", "i")
+        );
+    }
+
+    #[test]
+    fn test_unlambda_equivalence() {
+        let context = Context::default();
+        let ids = ids();
+        let ids: [&Identifier; 8] = ids.iter().collect::<Vec<_>>().try_into().unwrap();
+
+        for name in ["NOT", "XOR", "SUCC", "ADD", "MUL", "PRED", "SUB"] {
+            let ski = unlambda_recursive(&context, expr::v(name));
+            let turner = unlambda(&context, expr::v(name), &ids);
+
+            assert_eq!(
+                equivalence(&context, ski.clone(), turner.clone(), 10000),
+                Ok(Equivalence::Equal),
+                "{}",
+                name
+            );
+            assert!(size(&turner) <= size(&ski), "{}", name);
+        }
+
+        // SKI への変換では大きくなりやすい式ほど差が開く
+        let ski = unlambda_recursive(&context, expr::v("PRED"));
+        let turner = unlambda(&context, expr::v("PRED"), &ids);
+        assert!(size(&turner) * 2 < size(&ski));
+    }
+}
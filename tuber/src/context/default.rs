@@ -12,6 +12,23 @@ impl Default for Context {
                 vec!["x", "y", "z"],
                 expr::a(expr::a("x", "z"), expr::a("y", "z")),
             ),
+            func::new("b", vec!["x", "y", "z"], expr::a("x", expr::a("y", "z"))),
+            func::new("c", vec!["x", "y", "z"], expr::a(expr::a("x", "z"), "y")),
+            func::new(
+                "s'",
+                vec!["x", "y", "z", "w"],
+                expr::a(expr::a("x", expr::a("y", "w")), expr::a("z", "w")),
+            ),
+            func::new(
+                "b'",
+                vec!["x", "y", "z", "w"],
+                expr::a(expr::a("x", "y"), expr::a("z", "w")),
+            ),
+            func::new(
+                "c'",
+                vec!["x", "y", "z", "w"],
+                expr::a(expr::a("x", expr::a("y", "w")), "z"),
+            ),
             func::new(
                 "ι",
                 vec!["f"],
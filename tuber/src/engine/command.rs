@@ -14,6 +14,11 @@ pub enum Command {
     Query(Identifier),     // Context から定義済み関数を検索
     Context,               // Context 全体を表示
     Unlambda(u8, Expr),    // Expr からラムダ抽象を除去する
+    UnlambdaCompare(Expr), // すべての戦略でラムダ抽象を除去し、結果の大きさを比べる
+    UnlambdaContext(u8),   // Context のすべての関数からラムダ抽象を除去する
+    Equiv(Expr, Expr),     // 2つの式の正規形がα同値か判定する
+    BitLength(Expr),       // BLC で表現したときのビット長を表示
+    Type(Expr),            // 式の型を推論する
 }
 
 #[cfg(test)]
@@ -60,3 +65,28 @@ pub fn context() -> Command {
 pub fn unlambda<E: Into<Expr>>(level: u8, expr: E) -> Command {
     Command::Unlambda(level, expr.into())
 }
+
+#[cfg(test)]
+pub fn unlambda_compare<E: Into<Expr>>(expr: E) -> Command {
+    Command::UnlambdaCompare(expr.into())
+}
+
+#[cfg(test)]
+pub fn unlambda_context(level: u8) -> Command {
+    Command::UnlambdaContext(level)
+}
+
+#[cfg(test)]
+pub fn equiv<L: Into<Expr>, R: Into<Expr>>(lhs: L, rhs: R) -> Command {
+    Command::Equiv(lhs.into(), rhs.into())
+}
+
+#[cfg(test)]
+pub fn type_of<E: Into<Expr>>(expr: E) -> Command {
+    Command::Type(expr.into())
+}
+
+#[cfg(test)]
+pub fn bit_length<E: Into<Expr>>(expr: E) -> Command {
+    Command::BitLength(expr.into())
+}
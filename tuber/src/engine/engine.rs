@@ -1,67 +1,148 @@
 use super::Command;
 use crate::calc::{
-    expand, unlambda_iota, unlambda_recursive, unlambda_recursive_, unlambda_shallow, Eval,
-    RecursiveStrategy,
+    blc_encode, equivalence, expand, infer_type, kiselyov_bulk_combinators, metrics,
+    unlambda_context, unlambda_iota, unlambda_recursive, unlambda_recursive_, Equivalence, Eval,
+    EvalStep, GrowthMonitor, Metrics, RecursiveStrategy, Strategy, Type,
 };
 use crate::context::Context;
+use crate::error::Error;
 use crate::expr::{Expr, Identifier};
 use crate::func::Func;
+use std::collections::VecDeque;
+
+/// EvalLast, EvalHead, EvalTail で簡約を打ち切るまでのステップ数の既定値
+pub const STEP_LIMIT: usize = 10000;
+
+/// UnlambdaCompare で比べる Unlambda のレベル
+///
+/// レベル 1 は定義済みの関数を展開するだけでラムダ抽象を除去しないので含めない
+const COMPARED_LEVELS: std::ops::RangeInclusive<u8> = 2..=8;
 
 pub struct Engine {
     context: Context,
+    step_limit: usize,
+    strategy: Strategy,
+    eta_reduction: bool,
+    cycle_detection: bool,
 }
 
 impl Engine {
     pub fn new(context: Context) -> Self {
-        Self { context }
+        Self {
+            context,
+            step_limit: STEP_LIMIT,
+            strategy: Strategy::default(),
+            eta_reduction: false,
+            cycle_detection: false,
+        }
+    }
+
+    /// Eval, EvalLast, EvalHead, EvalTail で用いる評価戦略を指定する
+    pub fn with_strategy(self, strategy: Strategy) -> Self {
+        Self { strategy, ..self }
+    }
+
+    /// Eval, EvalLast, EvalHead, EvalTail で β 簡約に加えて η 簡約を行うようにする
+    pub fn with_eta_reduction(self) -> Self {
+        Self {
+            eta_reduction: true,
+            ..self
+        }
+    }
+
+    /// EvalLast, EvalHead, EvalTail で以前と同じ式が再び現れたら簡約を打ち切るようにする
+    ///
+    /// ステップごとに式を記録するので、簡約が長く続くほど時間と記憶領域を使う
+    pub fn with_cycle_detection(self) -> Self {
+        Self {
+            cycle_detection: true,
+            ..self
+        }
     }
 
-    pub fn run(self, command: Command) -> RunResult {
+    /// EvalLast, EvalHead, EvalTail, Equiv で簡約を打ち切るまでのステップ数を指定する
+    pub fn with_step_limit(self, step_limit: usize) -> Self {
+        Self { step_limit, ..self }
+    }
+
+    pub fn run(self, command: Command) -> Result<RunResult, Error> {
         match command {
             Command::Del(id) => {
                 let mut context = self.context;
                 context.del(&id);
-                RunResult::Del {
+                Ok(RunResult::Del {
                     input: id,
                     result: context,
-                }
+                })
             }
 
             Command::Update(func) => {
                 let mut context = self.context;
                 context.def(func.clone());
-                RunResult::Update {
+                Ok(RunResult::Update {
                     input: func,
                     result: context,
-                }
+                })
             }
 
             Command::Eval(expr) => {
-                let eval = Eval::new(self.context, expr.clone());
-
-                RunResult::Eval { input: expr, eval }
-            }
-
-            // Command::EvalLast(e) => {
-            //     match &self.display_style {
-            //         Style::LazyK => println!("{}", LazyKStyle(&e)),
-            //         Style::EcmaScript => println!("{}", EcmaScriptStyle(&e)),
-            //         _ => unreachable!(),
-            //     }
-
-            //     let mut steps = Eval::new(e, &self.context);
-            //     if let (Some(e), _continue) = steps.eval_last(100) {
-            //         println!("→ ...");
-            //         match &self.display_style {
-            //             Style::LazyK => println!("→ {}", LazyKStyle(&e)),
-            //             Style::EcmaScript => println!("→ {}", EcmaScriptStyle(&e)),
-            //             _ => unreachable!(),
-            //         }
-            //     } else {
-            //         // TODO
-            //     }
-            // }
-            Command::Query(id) => self.context.get(&id).map_or(
+                let eval = self.eval(expr.clone());
+
+                Ok(RunResult::Eval { input: expr, eval })
+            }
+
+            Command::EvalLast(expr) => {
+                let step_limit = self.step_limit;
+                let mut eval = self.eval(expr.clone()).with_step_limit(step_limit);
+
+                let mut result = None;
+                let outcome = drive(&mut eval, usize::MAX, |step| result = Some(step))?;
+
+                Ok(RunResult::EvalLast {
+                    input: expr,
+                    result,
+                    outcome,
+                })
+            }
+
+            Command::EvalHead(len, expr) => {
+                let step_limit = self.step_limit;
+                let mut eval = self.eval(expr.clone()).with_step_limit(step_limit);
+
+                let mut result = Vec::new();
+                let outcome = drive(&mut eval, len, |step| result.push(step))?;
+
+                Ok(RunResult::EvalHead {
+                    input: expr,
+                    result,
+                    outcome,
+                })
+            }
+
+            Command::EvalTail(len, expr) => {
+                let step_limit = self.step_limit;
+                let mut eval = self.eval(expr.clone()).with_step_limit(step_limit);
+
+                // 途中のステップは保持せず、末尾の len ステップ分だけをリングバッファに残す
+                let mut tail: VecDeque<EvalStep> = VecDeque::with_capacity(len);
+                let outcome = drive(&mut eval, usize::MAX, |step| {
+                    if len == 0 {
+                        return;
+                    }
+                    if tail.len() == len {
+                        tail.pop_front();
+                    }
+                    tail.push_back(step);
+                })?;
+
+                Ok(RunResult::EvalTail {
+                    input: expr,
+                    result: tail.into(),
+                    outcome,
+                })
+            }
+
+            Command::Query(id) => Ok(self.context.get(&id).map_or(
                 RunResult::Query {
                     input: id.clone(),
                     result: None,
@@ -70,27 +151,223 @@ impl Engine {
                     input: id.clone(),
                     result: Some(func.clone()),
                 },
-            ),
+            )),
 
-            Command::Context => RunResult::Context {
+            Command::Context => Ok(RunResult::Context {
                 result: self.context,
-            },
-
-            Command::Unlambda(level, e) => RunResult::Unlambda {
-                input: e.clone(),
-                level,
-                result: match level {
-                    1 => expand(&self.context, e),
-                    2 => unlambda_recursive(&self.context, e),
-                    3 => unlambda_recursive_(&RecursiveStrategy::SK, &self.context, e),
-                    4 => unlambda_iota(&self.context, e),
-                    _ => panic!("not implemented"),
-                },
-            },
+            }),
+
+            Command::Unlambda(level, e) => {
+                let result = unlambda(&self.context, level, e.clone())?;
+                let definitions = definitions(level, &result);
+                Ok(RunResult::Unlambda {
+                    input: e,
+                    level,
+                    metrics: metrics(&with_definitions(&self.context, &definitions), &result),
+                    result,
+                    definitions,
+                })
+            }
+
+            Command::UnlambdaCompare(e) => {
+                let result = COMPARED_LEVELS
+                    .map(|level| {
+                        let result = unlambda(&self.context, level, e.clone())?;
+                        let context = with_definitions(&self.context, &definitions(level, &result));
+                        let metrics = metrics(&context, &result);
+                        Ok((level, result, metrics))
+                    })
+                    .collect::<Result<_, Error>>()?;
+                Ok(RunResult::UnlambdaCompare { input: e, result })
+            }
 
-            _ => panic!("not implemented"),
+            Command::UnlambdaContext(level) => {
+                let strategy = recursive_strategy(level).ok_or(Error::UnsupportedLevel(level))?;
+                Ok(RunResult::UnlambdaContext {
+                    level,
+                    result: unlambda_context(&strategy, &self.context),
+                })
+            }
+
+            Command::Equiv(lhs, rhs) => {
+                let result = equivalence(&self.context, lhs.clone(), rhs.clone(), self.step_limit)?;
+                Ok(RunResult::Equiv { lhs, rhs, result })
+            }
+
+            Command::BitLength(expr) => {
+                let bits = blc_encode(&self.context, expr.clone())?;
+                Ok(RunResult::BitLength {
+                    input: expr,
+                    length: bits.len(),
+                    bits,
+                })
+            }
+
+            Command::Type(expr) => {
+                let result = infer_type(&self.context, &expr)?;
+                Ok(RunResult::Type {
+                    input: expr,
+                    result,
+                })
+            }
         }
     }
+
+    /// コマンドを順に実行し、すべての結果を返す
+    ///
+    /// Update と Del で書き換えた Context と、Unlambda の変換結果が使う定義を後続のコマンドに引き継ぐ
+    /// 実行に失敗したコマンドがあっても、残りのコマンドは実行する
+    pub fn run_script(
+        self,
+        commands: impl IntoIterator<Item = Command>,
+    ) -> Vec<Result<RunResult, Error>> {
+        let Self {
+            mut context,
+            step_limit,
+            strategy,
+            eta_reduction,
+            cycle_detection,
+        } = self;
+
+        commands
+            .into_iter()
+            .map(|command| {
+                let engine = Self {
+                    context: context.clone(),
+                    step_limit,
+                    strategy,
+                    eta_reduction,
+                    cycle_detection,
+                };
+                let result = engine.run(command);
+                match &result {
+                    Ok(RunResult::Update { result, .. } | RunResult::Del { result, .. }) => {
+                        context = result.clone();
+                    }
+                    Ok(RunResult::Unlambda { definitions, .. }) => {
+                        context = with_definitions(&context, definitions);
+                    }
+                    _ => {}
+                }
+                result
+            })
+            .collect()
+    }
+
+    fn eval(self, expr: Expr) -> Eval {
+        let mut eval = Eval::new(self.context, expr, self.strategy);
+        if self.cycle_detection {
+            eval = eval.with_cycle_detection();
+        }
+        if self.eta_reduction {
+            eval = eval.with_eta_reduction();
+        }
+        eval
+    }
+}
+
+/// level に対応する戦略で式からラムダ抽象を除去する
+fn unlambda(context: &Context, level: u8, e: Expr) -> Result<Expr, Error> {
+    Ok(match level {
+        1 => expand(context, e)?,
+        2 => unlambda_recursive(context, e),
+        3 => unlambda_recursive_(&RecursiveStrategy::SK, context, e),
+        4 => unlambda_iota(context, e),
+        5 => unlambda_recursive_(&RecursiveStrategy::Turner, context, e),
+        6 => unlambda_recursive_(&RecursiveStrategy::Kiselyov, context, e),
+        7 => unlambda_recursive_(&RecursiveStrategy::KiselyovEta, context, e),
+        8 => unlambda_recursive_(&RecursiveStrategy::KiselyovBulk, context, e),
+        _ => return Err(Error::UnsupportedLevel(level)),
+    })
+}
+
+/// level の変換結果が使うコンビネータのうち、Context の外で定義が必要なもの
+fn definitions(level: u8, result: &Expr) -> Vec<Func> {
+    match level {
+        8 => kiselyov_bulk_combinators(result),
+        _ => Vec::new(),
+    }
+}
+
+fn with_definitions(context: &Context, definitions: &[Func]) -> Context {
+    let mut context = context.clone();
+    for func in definitions {
+        context.def(func.clone());
+    }
+    context
+}
+
+/// UnlambdaContext で level に対応するラムダ抽象の除去の戦略
+fn recursive_strategy(level: u8) -> Option<RecursiveStrategy> {
+    match level {
+        2 => Some(RecursiveStrategy::SKI),
+        3 => Some(RecursiveStrategy::SK),
+        4 => Some(RecursiveStrategy::Iota),
+        5 => Some(RecursiveStrategy::Turner),
+        6 => Some(RecursiveStrategy::Kiselyov),
+        7 => Some(RecursiveStrategy::KiselyovEta),
+        8 => Some(RecursiveStrategy::KiselyovBulk),
+        _ => None,
+    }
+}
+
+/// 簡約を最大 len ステップ進め、各ステップを visit に渡す
+///
+/// 簡約がどのように終わったかを返す
+fn drive(eval: &mut Eval, len: usize, mut visit: impl FnMut(EvalStep)) -> Result<Outcome, Error> {
+    let mut growth_monitor = GrowthMonitor::new();
+    for step in eval.by_ref().take(len) {
+        match step {
+            Ok(step) => {
+                growth_monitor.observe(&step.expr);
+                visit(step);
+            }
+            Err(Error::StepLimitExceeded(_)) => {
+                return Ok(Outcome::StepLimitExceeded {
+                    likely_non_terminating: growth_monitor.likely_non_terminating(),
+                })
+            }
+            Err(Error::Diverged {
+                cycle_start,
+                period,
+            }) => {
+                return Ok(Outcome::Diverged {
+                    cycle_start,
+                    period,
+                })
+            }
+            Err(err) => return Err(err),
+        }
+    }
+    match eval.next_path() {
+        Some(_) => Ok(Outcome::Truncated),
+        None => Ok(Outcome::Normalized),
+    }
+}
+
+/// EvalLast, EvalHead, EvalTail で簡約がどのように終わったかを表現する
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    /// これ以上簡約できない式に達した
+    Normalized,
+
+    /// 指定されたステップ数だけ簡約したが、まだ簡約できる
+    Truncated,
+
+    /// ステップ数の上限に達して簡約を打ち切った
+    ///
+    /// 式の大きさが単調に増え続けていた場合 likely_non_terminating が true になる
+    StepLimitExceeded { likely_non_terminating: bool },
+
+    /// cycle_start ステップ目の式が period ステップごとに繰り返し現れ、簡約が停止しない
+    Diverged { cycle_start: usize, period: usize },
+}
+
+impl Outcome {
+    /// 簡約が最後まで終わらずに打ち切られたかどうか
+    pub fn truncated(&self) -> bool {
+        !matches!(self, Outcome::Normalized)
+    }
 }
 
 pub enum RunResult {
@@ -106,6 +383,21 @@ pub enum RunResult {
         input: Expr,
         eval: Eval,
     },
+    EvalLast {
+        input: Expr,
+        result: Option<EvalStep>,
+        outcome: Outcome,
+    },
+    EvalHead {
+        input: Expr,
+        result: Vec<EvalStep>,
+        outcome: Outcome,
+    },
+    EvalTail {
+        input: Expr,
+        result: Vec<EvalStep>,
+        outcome: Outcome,
+    },
     Query {
         input: Identifier,
         result: Option<Func>,
@@ -117,5 +409,520 @@ pub enum RunResult {
         input: Expr,
         level: u8,
         result: Expr,
+        metrics: Metrics,
+        /// result を評価するために Context に追加で定義が必要な関数
+        definitions: Vec<Func>,
+    },
+    UnlambdaCompare {
+        input: Expr,
+        result: Vec<(u8, Expr, Metrics)>,
+    },
+    UnlambdaContext {
+        level: u8,
+        result: Context,
+    },
+    Equiv {
+        lhs: Expr,
+        rhs: Expr,
+        result: Equivalence,
     },
+    BitLength {
+        input: Expr,
+        bits: String,
+        length: usize,
+    },
+    Type {
+        input: Expr,
+        result: Type,
+    },
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::command;
+    use crate::expr;
+    use crate::func;
+    use crate::to_string::{DisplayStyle, Format};
+
+    fn setup() -> Context {
+        let i = func::new("i", vec!["x"], "x");
+        let k = func::new("k", vec!["x", "y"], "x");
+        let s = func::new(
+            "s",
+            vec!["x", "y", "z"],
+            expr::a(expr::a("x", "z"), expr::a("y", "z")),
+        );
+        let omega = func::new("OMEGA", Vec::<&str>::new(), expr::a(expr::a("s", "i"), "i"));
+
+        Context::from(vec![i, k, s, omega])
+    }
+
+    fn exprs(steps: &[EvalStep]) -> Vec<Expr> {
+        steps.iter().map(|step| step.expr.clone()).collect()
+    }
+
+    #[test]
+    fn test_eval_last() {
+        let engine = Engine::new(setup());
+
+        // ```skk:a => ``k:a`k:a => :a
+        let result = engine.run(command::eval_last(expr::a(
+            expr::a(expr::a("s", "k"), "k"),
+            ":a",
+        )));
+        let result = result.unwrap();
+
+        match result {
+            RunResult::EvalLast {
+                result, outcome, ..
+            } => {
+                let step = result.unwrap();
+                assert_eq!(step.step, 2);
+                assert_eq!(step.expr, expr::s("a"));
+                assert_eq!(outcome, Outcome::Normalized);
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_eval_last_irreducible() {
+        let engine = Engine::new(setup());
+
+        let result = engine.run(command::eval_last(expr::a(":a", ":b"))).unwrap();
+
+        match result {
+            RunResult::EvalLast {
+                result, outcome, ..
+            } => {
+                assert_eq!(result, None);
+                assert_eq!(outcome, Outcome::Normalized);
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_eval_last_truncated() {
+        let engine = Engine::new(setup()).with_step_limit(100);
+
+        // ``OMEGA OMEGA は停止しない
+        let result = engine
+            .run(command::eval_last(expr::a("OMEGA", "OMEGA")))
+            .unwrap();
+
+        match result {
+            RunResult::EvalLast {
+                result, outcome, ..
+            } => {
+                assert_eq!(result.map(|step| step.step), Some(100));
+                assert_eq!(
+                    outcome,
+                    Outcome::StepLimitExceeded {
+                        likely_non_terminating: true
+                    }
+                );
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_eval_head() {
+        let engine = Engine::new(setup());
+
+        // ```s`i:a`i:b:c => ```i:a:c``i:b:c => ``:a:c``i:b:c => ``:a:c`:b:c
+        let input = expr::a(
+            expr::a(expr::a("s", expr::a("i", ":a")), expr::a("i", ":b")),
+            ":c",
+        );
+
+        match engine.run(command::eval_head(2, input.clone())).unwrap() {
+            RunResult::EvalHead {
+                result, outcome, ..
+            } => {
+                assert_eq!(
+                    exprs(&result),
+                    vec![
+                        expr::a(
+                            expr::a(expr::a("i", ":a"), ":c"),
+                            expr::a(expr::a("i", ":b"), ":c")
+                        ),
+                        expr::a(expr::a(":a", ":c"), expr::a(expr::a("i", ":b"), ":c")),
+                    ]
+                );
+                assert_eq!(outcome, Outcome::Truncated);
+            }
+            _ => panic!("unexpected result"),
+        }
+
+        let engine = Engine::new(setup());
+        match engine.run(command::eval_head(10, input)).unwrap() {
+            RunResult::EvalHead {
+                result, outcome, ..
+            } => {
+                assert_eq!(result.len(), 3);
+                assert_eq!(outcome, Outcome::Normalized);
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_eval_tail() {
+        let engine = Engine::new(setup());
+
+        let input = expr::a(
+            expr::a(expr::a("s", expr::a("i", ":a")), expr::a("i", ":b")),
+            ":c",
+        );
+
+        match engine.run(command::eval_tail(2, input)).unwrap() {
+            RunResult::EvalTail {
+                result, outcome, ..
+            } => {
+                assert_eq!(
+                    exprs(&result),
+                    vec![
+                        expr::a(expr::a(":a", ":c"), expr::a(expr::a("i", ":b"), ":c")),
+                        expr::a(expr::a(":a", ":c"), expr::a(":b", ":c")),
+                    ]
+                );
+                assert_eq!(
+                    result.iter().map(|step| step.step).collect::<Vec<_>>(),
+                    vec![2, 3]
+                );
+                assert_eq!(outcome, Outcome::Normalized);
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_eval_tail_truncated() {
+        let engine = Engine::new(setup()).with_step_limit(50);
+
+        match engine
+            .run(command::eval_tail(3, expr::a("OMEGA", "OMEGA")))
+            .unwrap()
+        {
+            RunResult::EvalTail {
+                result, outcome, ..
+            } => {
+                assert_eq!(
+                    result.iter().map(|step| step.step).collect::<Vec<_>>(),
+                    vec![48, 49, 50]
+                );
+                assert!(matches!(outcome, Outcome::StepLimitExceeded { .. }));
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_unlambda_unsupported_level() {
+        let engine = Engine::new(setup());
+
+        let result = engine.run(command::unlambda(9, expr::l("x", "x")));
+
+        assert_eq!(result.err(), Some(Error::UnsupportedLevel(9)));
+    }
+
+    #[test]
+    fn test_unlambda_metrics() {
+        let engine = Engine::new(setup());
+
+        // ^x.^y.x => k
+        let result = engine
+            .run(command::unlambda(2, expr::l("x", expr::l("y", "x"))))
+            .unwrap();
+
+        match result {
+            RunResult::Unlambda {
+                result, metrics, ..
+            } => {
+                assert_eq!(result, expr::v("k"));
+                assert_eq!(metrics.nodes, 1);
+                assert_eq!(metrics.combinators, vec![(Identifier::from("k"), 1)]);
+                assert_eq!(metrics.blc_length, Some(7));
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_unlambda_bulk() {
+        let engine = Engine::new(Context::default());
+
+        // Bn, Cn, Sn の定義を添えて返す
+        let (result, definitions) = match engine.run(command::unlambda(8, "SUB")).unwrap() {
+            RunResult::Unlambda {
+                result,
+                definitions,
+                metrics,
+                ..
+            } => {
+                assert!(metrics.blc_length.is_some());
+                (result, definitions)
+            }
+            _ => panic!("unexpected result"),
+        };
+        assert!(!definitions.is_empty());
+
+        // 定義を引き継いだ Context で変換結果を評価できる
+        // ````SUB 3 1 :f :x => `:f`:f:x
+        let expr = expr::a(expr::a(expr::a(expr::a(result, "3"), "1"), ":f"), ":x");
+        let results = Engine::new(Context::default())
+            .run_script(vec![command::unlambda(8, "SUB"), command::eval_last(expr)]);
+        match &results[1] {
+            Ok(RunResult::EvalLast {
+                result: Some(step), ..
+            }) => assert_eq!(step.expr, expr::a(":f", expr::a(":f", ":x"))),
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_unlambda_compare() {
+        let engine = Engine::new(Context::default());
+
+        let result = engine.run(command::unlambda_compare("PRED")).unwrap();
+
+        match result {
+            RunResult::UnlambdaCompare { result, .. } => {
+                assert_eq!(
+                    result.iter().map(|(level, ..)| *level).collect::<Vec<_>>(),
+                    vec![2, 3, 4, 5, 6, 7, 8]
+                );
+                for (level, expr, metrics) in &result {
+                    let context =
+                        with_definitions(&Context::default(), &kiselyov_bulk_combinators(expr));
+                    assert_eq!(metrics, &crate::calc::metrics(&context, expr), "{}", level);
+                    assert!(metrics.blc_length.is_some(), "{}", level);
+                }
+
+                // Turner の規則を使うと SKI よりも小さくなる
+                let nodes = |level: u8| result[usize::from(level) - 2].2.nodes;
+                assert!(nodes(5) < nodes(2));
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_unlambda_context() {
+        let mut context = setup();
+        context.def(func::new("TRUE", vec!["x", "y"], "x"));
+        let engine = Engine::new(context);
+
+        match engine.run(command::unlambda_context(2)).unwrap() {
+            RunResult::UnlambdaContext { result, .. } => {
+                assert_eq!(
+                    result.get(&"TRUE".into()),
+                    Some(&func::new("TRUE", Vec::<&str>::new(), "k"))
+                );
+                assert_eq!(result.get(&"OMEGA".into()).map(|f| f.arity()), Some(0));
+            }
+            _ => panic!("unexpected result"),
+        }
+
+        // 展開するだけのレベル 1 では Context を書き換えられない
+        let engine = Engine::new(setup());
+        let result = engine.run(command::unlambda_context(1));
+        assert_eq!(result.err(), Some(Error::UnsupportedLevel(1)));
+    }
+
+    #[test]
+    fn test_equiv() {
+        let engine = Engine::new(Context::default());
+
+        // ``ADD 2 3 と 5 はどちらも同じ正規形に簡約される
+        let result = engine
+            .run(command::equiv(expr::a(expr::a("ADD", "2"), "3"), "5"))
+            .unwrap();
+
+        match result {
+            RunResult::Equiv { result, .. } => assert_eq!(result, Equivalence::Equal),
+            _ => panic!("unexpected result"),
+        }
+
+        // ステップ数の上限までに正規形が得られなければ判定できない
+        let engine = Engine::new(setup()).with_step_limit(100);
+        let result = engine
+            .run(command::equiv(expr::a("OMEGA", "OMEGA"), "i"))
+            .unwrap();
+
+        match result {
+            RunResult::Equiv { result, .. } => assert_eq!(result, Equivalence::Unknown),
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_bit_length() {
+        let engine = Engine::new(setup());
+
+        // ``skk は λx.λy.λz.``xz`yz と λx.λy.x を展開してから変換する
+        let result = engine
+            .run(command::bit_length(expr::a(expr::a("s", "k"), "k")))
+            .unwrap();
+
+        match result {
+            RunResult::BitLength { bits, length, .. } => {
+                assert_eq!(bits, "01010000000101111010011101000001100000110");
+                assert_eq!(length, 41);
+            }
+            _ => panic!("unexpected result"),
+        }
+
+        // シンボルは BLC で表現できない
+        let engine = Engine::new(setup());
+        let result = engine.run(command::bit_length(expr::a("i", ":a")));
+        assert_eq!(result.err(), Some(Error::NotEncodable(expr::s("a"))));
+    }
+
+    #[test]
+    fn test_type() {
+        let engine = Engine::new(setup());
+
+        // `ki : a -> b -> b
+        match engine.run(command::type_of(expr::a("k", "i"))).unwrap() {
+            RunResult::Type { result, .. } => {
+                assert_eq!(result.to_string(), "a -> b -> b");
+                assert_eq!(
+                    result.format(&DisplayStyle::EcmaScript),
+                    "(x: A) => (x: B) => B"
+                );
+            }
+            _ => panic!("unexpected result"),
+        }
+
+        let engine = Engine::new(setup());
+        let result = engine.run(command::type_of("OMEGA"));
+        assert!(matches!(result.err(), Some(Error::OccursCheck { .. })));
+    }
+
+    #[test]
+    fn test_run_script() {
+        let engine = Engine::new(setup());
+
+        let results = engine.run_script(vec![
+            command::update(func::new(
+                "TWICE",
+                vec!["f", "x"],
+                expr::a("f", expr::a("f", "x")),
+            )),
+            command::eval_last(expr::a(expr::a("TWICE", "i"), ":a")),
+            command::del("TWICE"),
+            command::eval_last(expr::a(expr::a("TWICE", "i"), ":a")),
+            command::unlambda(9, "i"),
+            command::query("TWICE"),
+        ]);
+        assert_eq!(results.len(), 6);
+
+        // 定義した関数を後続のコマンドで使える
+        match &results[1] {
+            Ok(RunResult::EvalLast {
+                result: Some(step), ..
+            }) => assert_eq!(step.expr, expr::s("a")),
+            _ => panic!("unexpected result"),
+        }
+
+        // 削除した関数は簡約されない
+        match &results[3] {
+            Ok(RunResult::EvalLast { result, .. }) => assert!(result.is_none()),
+            _ => panic!("unexpected result"),
+        }
+
+        // 失敗したコマンドがあっても続きを実行する
+        assert_eq!(results[4].as_ref().err(), Some(&Error::UnsupportedLevel(9)));
+        match &results[5] {
+            Ok(RunResult::Query { result, .. }) => assert_eq!(result, &None),
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_eval_last_diverged() {
+        let engine = Engine::new(setup()).with_cycle_detection();
+
+        // `^x.`xx^x.`xx は 1 ステップで元の式に戻る
+        let omega = expr::l("x", expr::a("x", "x"));
+        let result = engine
+            .run(command::eval_last(expr::a(omega.clone(), omega.clone())))
+            .unwrap();
+
+        match result {
+            RunResult::EvalLast {
+                result, outcome, ..
+            } => {
+                assert_eq!(result.map(|step| step.step), Some(1));
+                assert_eq!(
+                    outcome,
+                    Outcome::Diverged {
+                        cycle_start: 0,
+                        period: 1
+                    }
+                );
+                assert!(outcome.truncated());
+            }
+            _ => panic!("unexpected result"),
+        }
+
+        // 指定しなければ循環を検出せず、ステップ数の上限まで簡約する
+        let engine = Engine::new(setup()).with_step_limit(100);
+        let result = engine
+            .run(command::eval_last(expr::a(omega.clone(), omega)))
+            .unwrap();
+        match result {
+            RunResult::EvalLast { outcome, .. } => {
+                assert!(matches!(outcome, Outcome::StepLimitExceeded { .. }))
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
+
+    #[test]
+    fn test_eval_last_likely_non_terminating() {
+        let engine = Engine::new(Context::default()).with_step_limit(200);
+
+        // `Y:f => `:f`:f`:f... と式が大きくなり続ける
+        let result = engine.run(command::eval_last(expr::a("Y", ":f"))).unwrap();
+
+        match result {
+            RunResult::EvalLast { outcome, .. } => {
+                assert_eq!(
+                    outcome,
+                    Outcome::StepLimitExceeded {
+                        likely_non_terminating: true
+                    }
+                );
+            }
+            _ => panic!("unexpected result"),
+        }
+
+        // 推定に足るだけのステップ数を簡約していなければ、停止しないとは推定しない
+        let engine = Engine::new(setup()).with_step_limit(1);
+        let result = engine
+            .run(command::eval_last(expr::a(
+                expr::a(expr::a(expr::a("s", "i"), "i"), expr::a("i", ":a")),
+                ":b",
+            )))
+            .unwrap();
+
+        match result {
+            RunResult::EvalLast { outcome, .. } => {
+                assert_eq!(
+                    outcome,
+                    Outcome::StepLimitExceeded {
+                        likely_non_terminating: false
+                    }
+                );
+            }
+            _ => panic!("unexpected result"),
+        }
+    }
 }
@@ -2,4 +2,4 @@ pub mod command;
 mod engine;
 
 pub use command::Command;
-pub use engine::{Engine, RunResult};
+pub use engine::{Engine, Outcome, RunResult};
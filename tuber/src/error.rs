@@ -0,0 +1,135 @@
+use crate::calc::Type;
+use crate::expr::{Expr, Identifier};
+use std::fmt::Display;
+
+/// 簡約やコマンドの実行中に起こりうるエラーを表現する
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// Context に定義されていない関数を適用しようとした
+    UndefinedFunction(Identifier),
+
+    /// 関数の arity と与えられた引数の個数が一致しない
+    ArityMismatch { expected: usize, actual: usize },
+
+    /// 関数として振る舞えない式を適用しようとした
+    NotAFunction(Expr),
+
+    /// 未対応の unlambda レベルが指定された
+    UnsupportedLevel(u8),
+
+    /// 簡約のステップ数が上限に達した
+    StepLimitExceeded(usize),
+
+    /// cycle_start ステップ目の式が period ステップごとに繰り返し現れ、簡約が停止しない
+    Diverged { cycle_start: usize, period: usize },
+
+    /// BLC や Jot などの変換先で表現できない式を変換しようとした
+    NotEncodable(Expr),
+
+    /// Church 数として解釈できない式が出力された
+    NotANumeral(Expr),
+
+    /// 型変数 var を、var 自身を含む型 ty と同一視しなければ型を付けられない
+    OccursCheck { var: Type, ty: Type },
+
+    /// 自分自身を直接または間接に参照する関数を展開しようとした
+    RecursiveDefinition(Identifier),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UndefinedFunction(id) => write!(f, "Undefined function: {}", id),
+            Error::ArityMismatch { expected, actual } => write!(
+                f,
+                "Number of arguments is mismatch: {} arg(s) expected, but {} given",
+                expected, actual
+            ),
+            Error::NotAFunction(expr) => write!(f, "Not a function: {}", expr),
+            Error::UnsupportedLevel(level) => write!(f, "Unsupported unlambda level: {}", level),
+            Error::StepLimitExceeded(limit) => write!(f, "Step limit exceeded: {} steps", limit),
+            Error::Diverged {
+                cycle_start,
+                period,
+            } => write!(
+                f,
+                "Diverged: the expression at step {} recurs every {} step(s)",
+                cycle_start, period
+            ),
+            Error::NotEncodable(expr) => {
+                write!(f, "Cannot be encoded: {}", expr)
+            }
+            Error::NotANumeral(expr) => write!(f, "Not a Church numeral: {}", expr),
+            Error::OccursCheck { var, ty } => write!(
+                f,
+                "Occurs check failed: cannot construct the infinite type {} = {}",
+                var, ty
+            ),
+            Error::RecursiveDefinition(id) => {
+                write!(f, "Cannot expand recursive definition: {}", id)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            Error::UndefinedFunction("f".into()).to_string(),
+            "Undefined function: f"
+        );
+        assert_eq!(
+            Error::ArityMismatch {
+                expected: 2,
+                actual: 1
+            }
+            .to_string(),
+            "Number of arguments is mismatch: 2 arg(s) expected, but 1 given"
+        );
+        assert_eq!(
+            Error::NotAFunction(expr::s("a")).to_string(),
+            "Not a function: :a"
+        );
+        assert_eq!(
+            Error::UnsupportedLevel(5).to_string(),
+            "Unsupported unlambda level: 5"
+        );
+        assert_eq!(
+            Error::StepLimitExceeded(100).to_string(),
+            "Step limit exceeded: 100 steps"
+        );
+        assert_eq!(
+            Error::Diverged {
+                cycle_start: 3,
+                period: 2
+            }
+            .to_string(),
+            "Diverged: the expression at step 3 recurs every 2 step(s)"
+        );
+        assert_eq!(
+            Error::NotEncodable(expr::s("a")).to_string(),
+            "Cannot be encoded: :a"
+        );
+        assert_eq!(
+            Error::NotANumeral(expr::s("a")).to_string(),
+            "Not a Church numeral: :a"
+        );
+        assert_eq!(
+            Error::OccursCheck {
+                var: Type::Var(0),
+                ty: Type::arrow(Type::Var(0), Type::Var(1))
+            }
+            .to_string(),
+            "Occurs check failed: cannot construct the infinite type a = a -> b"
+        );
+    }
+}
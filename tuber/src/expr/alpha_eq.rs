@@ -0,0 +1,45 @@
+use crate::expr::{DeBruijnExpr, Expr};
+
+impl Expr {
+    /// 2つの式がα同値かどうかを判定する
+    ///
+    /// 束縛変数は名前の代わりに、束縛しているラムダ抽象までの距離 (de Bruijn index) で比較する
+    /// 自由変数とシンボルは名前で比較する
+    pub fn alpha_eq(&self, other: &Expr) -> bool {
+        DeBruijnExpr::from(self) == DeBruijnExpr::from(other)
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use crate::expr;
+
+    #[test]
+    fn test_alpha_eq() {
+        // ^x.x と ^y.y はα同値
+        assert!(expr::l("x", "x").alpha_eq(&expr::l("y", "y")));
+
+        // ^x.^y.`xy と ^y.^x.`yx はα同値
+        assert!(expr::l("x", expr::l("y", expr::a("x", "y")))
+            .alpha_eq(&expr::l("y", expr::l("x", expr::a("y", "x")))));
+
+        // ^x.^y.x と ^x.^y.y はα同値ではない
+        assert!(!expr::l("x", expr::l("y", "x")).alpha_eq(&expr::l("x", expr::l("y", "y"))));
+
+        // ^x.^x.x の x は内側のラムダ抽象に束縛される
+        assert!(expr::l("x", expr::l("x", "x")).alpha_eq(&expr::l("y", expr::l("z", "z"))));
+        assert!(!expr::l("x", expr::l("x", "x")).alpha_eq(&expr::l("y", expr::l("z", "y"))));
+
+        // 自由変数は名前で区別される
+        assert!(expr::l("x", "y").alpha_eq(&expr::l("z", "y")));
+        assert!(!expr::l("x", "y").alpha_eq(&expr::l("x", "z")));
+
+        // 自由変数と束縛変数は区別される
+        assert!(!expr::l("x", expr::a("x", "y")).alpha_eq(&expr::l("y", expr::a("y", "y"))));
+
+        // 変数とシンボルは区別される
+        assert!(!expr::v("a").alpha_eq(&expr::s("a")));
+    }
+}
@@ -0,0 +1,51 @@
+use crate::expr::{DeBruijnExpr, Expr};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+impl Expr {
+    /// α同値な式同士が同じ値になるハッシュ値を計算する
+    ///
+    /// 束縛変数は名前の代わりに、束縛しているラムダ抽象までの距離 (de Bruijn index) でハッシュする
+    pub fn alpha_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        DeBruijnExpr::from(self).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use crate::expr;
+
+    #[test]
+    fn test_alpha_hash() {
+        // ^x.x と ^y.y はα同値
+        assert_eq!(
+            expr::l("x", "x").alpha_hash(),
+            expr::l("y", "y").alpha_hash()
+        );
+
+        // ^x.^y.`xy と ^y.^x.`yx はα同値
+        assert_eq!(
+            expr::l("x", expr::l("y", expr::a("x", "y"))).alpha_hash(),
+            expr::l("y", expr::l("x", expr::a("y", "x"))).alpha_hash()
+        );
+
+        // ^x.^y.x と ^x.^y.y はα同値ではない
+        assert_ne!(
+            expr::l("x", expr::l("y", "x")).alpha_hash(),
+            expr::l("x", expr::l("y", "y")).alpha_hash()
+        );
+
+        // 自由変数は名前で区別される
+        assert_ne!(
+            expr::l("x", "y").alpha_hash(),
+            expr::l("x", "z").alpha_hash()
+        );
+
+        // 変数とシンボルは区別される
+        assert_ne!(expr::v("a").alpha_hash(), expr::s("a").alpha_hash());
+    }
+}
@@ -0,0 +1,380 @@
+use super::bound_vars::BoundVars;
+use crate::expr::{self, Expr, Identifier};
+use std::hash::{Hash, Hasher};
+
+/// 束縛変数を名前の代わりに de Bruijn index で表現したラムダ式
+///
+/// 束縛変数が参照するラムダ抽象を、内側から数えて何番目か (0 始まり) で表す
+/// 表示用に元の名前も保持するが、比較とハッシュ値の計算では名前を無視するので、α同値な式同士は等しくなる
+#[derive(Clone, Debug)]
+pub enum DeBruijnExpr {
+    Bound {
+        index: usize,
+        name: Identifier,
+    },
+    Free(Identifier),
+    Symbol(Identifier),
+    Apply {
+        lhs: Box<DeBruijnExpr>,
+        rhs: Box<DeBruijnExpr>,
+    },
+    Lambda {
+        param: Identifier,
+        body: Box<DeBruijnExpr>,
+    },
+}
+
+impl DeBruijnExpr {
+    /// cutoff 以上の index を持つ束縛変数 (式の外側で束縛されている変数) の index を d だけずらす
+    ///
+    /// ずらした index が負になる束縛変数があれば None を返す
+    pub fn shift(&self, d: isize, cutoff: usize) -> Option<Self> {
+        let shifted = match self {
+            DeBruijnExpr::Bound { index, name } if *index >= cutoff => DeBruijnExpr::Bound {
+                index: index.checked_add_signed(d)?,
+                name: name.clone(),
+            },
+            DeBruijnExpr::Apply { lhs, rhs } => DeBruijnExpr::Apply {
+                lhs: Box::new(lhs.shift(d, cutoff)?),
+                rhs: Box::new(rhs.shift(d, cutoff)?),
+            },
+            DeBruijnExpr::Lambda { param, body } => DeBruijnExpr::Lambda {
+                param: param.clone(),
+                body: Box::new(body.shift(d, cutoff + 1)?),
+            },
+            _ => self.clone(),
+        };
+        Some(shifted)
+    }
+
+    /// shift() と同じく index をずらす、増やす方向にしかずらさないので失敗しない
+    fn lift(&self, d: usize, cutoff: usize) -> Self {
+        match self {
+            DeBruijnExpr::Bound { index, name } if *index >= cutoff => DeBruijnExpr::Bound {
+                index: index + d,
+                name: name.clone(),
+            },
+            DeBruijnExpr::Apply { lhs, rhs } => DeBruijnExpr::Apply {
+                lhs: Box::new(lhs.lift(d, cutoff)),
+                rhs: Box::new(rhs.lift(d, cutoff)),
+            },
+            DeBruijnExpr::Lambda { param, body } => DeBruijnExpr::Lambda {
+                param: param.clone(),
+                body: Box::new(body.lift(d, cutoff + 1)),
+            },
+            _ => self.clone(),
+        }
+    }
+
+    /// index 番目の束縛変数を arg に置き換える
+    ///
+    /// ラムダ抽象の内側では index と arg の中の束縛変数をずらすので、変数の捕獲は起こらない
+    pub fn substitute(&self, index: usize, arg: &DeBruijnExpr) -> Self {
+        match self {
+            DeBruijnExpr::Bound { index: i, .. } if *i == index => arg.clone(),
+            DeBruijnExpr::Apply { lhs, rhs } => DeBruijnExpr::Apply {
+                lhs: Box::new(lhs.substitute(index, arg)),
+                rhs: Box::new(rhs.substitute(index, arg)),
+            },
+            DeBruijnExpr::Lambda { param, body } => DeBruijnExpr::Lambda {
+                param: param.clone(),
+                body: Box::new(body.substitute(index + 1, &arg.lift(1, 0))),
+            },
+            _ => self.clone(),
+        }
+    }
+
+    /// 式全体が β 簡約基 `^x.M N であれば、M の x を N に置き換えた式を返す
+    pub fn beta_reduce(&self) -> Option<Self> {
+        match self {
+            DeBruijnExpr::Apply { lhs, rhs } => match lhs.as_ref() {
+                // index 0 の束縛変数は置き換え済みなので、index を 1 つ減らしても負にはならない
+                DeBruijnExpr::Lambda { body, .. } => {
+                    body.substitute(0, &rhs.lift(1, 0)).shift(-1, 0)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl PartialEq for DeBruijnExpr {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (DeBruijnExpr::Bound { index: l, .. }, DeBruijnExpr::Bound { index: r, .. }) => l == r,
+            (DeBruijnExpr::Free(l), DeBruijnExpr::Free(r)) => l == r,
+            (DeBruijnExpr::Symbol(l), DeBruijnExpr::Symbol(r)) => l == r,
+            (
+                DeBruijnExpr::Apply { lhs: ll, rhs: lr },
+                DeBruijnExpr::Apply { lhs: rl, rhs: rr },
+            ) => ll == rl && lr == rr,
+            (DeBruijnExpr::Lambda { body: l, .. }, DeBruijnExpr::Lambda { body: r, .. }) => l == r,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for DeBruijnExpr {}
+
+impl Hash for DeBruijnExpr {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            DeBruijnExpr::Bound { index, .. } => {
+                0u8.hash(state);
+                index.hash(state);
+            }
+            DeBruijnExpr::Free(id) => {
+                1u8.hash(state);
+                id.hash(state);
+            }
+            DeBruijnExpr::Symbol(id) => {
+                2u8.hash(state);
+                id.hash(state);
+            }
+            DeBruijnExpr::Apply { lhs, rhs } => {
+                3u8.hash(state);
+                lhs.hash(state);
+                rhs.hash(state);
+            }
+            DeBruijnExpr::Lambda { body, .. } => {
+                4u8.hash(state);
+                body.hash(state);
+            }
+        }
+    }
+}
+
+// ========================================================================== //
+
+impl From<&Expr> for DeBruijnExpr {
+    fn from(expr: &Expr) -> Self {
+        from_expr(expr, &mut Vec::new())
+    }
+}
+
+impl From<Expr> for DeBruijnExpr {
+    fn from(expr: Expr) -> Self {
+        DeBruijnExpr::from(&expr)
+    }
+}
+
+fn from_expr<'a>(expr: &'a Expr, bound_vars: &mut Vec<&'a Identifier>) -> DeBruijnExpr {
+    match expr {
+        Expr::Variable(id) => match bound_vars.iter().rev().position(|param| *param == id) {
+            Some(index) => DeBruijnExpr::Bound {
+                index,
+                name: id.clone(),
+            },
+            None => DeBruijnExpr::Free(id.clone()),
+        },
+
+        Expr::Symbol(id) => DeBruijnExpr::Symbol(id.clone()),
+
+        Expr::Apply { lhs, rhs } => DeBruijnExpr::Apply {
+            lhs: Box::new(from_expr(lhs, bound_vars)),
+            rhs: Box::new(from_expr(rhs, bound_vars)),
+        },
+
+        Expr::Lambda { param, body } => {
+            bound_vars.push(param);
+            let body = from_expr(body, bound_vars);
+            bound_vars.pop();
+            DeBruijnExpr::Lambda {
+                param: param.clone(),
+                body: Box::new(body),
+            }
+        }
+    }
+}
+
+/// Expr に戻す
+///
+/// 束縛変数には元の名前を使う
+/// 置換によって元の名前が別の変数と衝突するようになった場合は、Identifier::rename でリネームする
+impl From<&DeBruijnExpr> for Expr {
+    fn from(expr: &DeBruijnExpr) -> Self {
+        to_expr(expr, &mut Vec::new())
+    }
+}
+
+impl From<DeBruijnExpr> for Expr {
+    fn from(expr: DeBruijnExpr) -> Self {
+        Expr::from(&expr)
+    }
+}
+
+fn to_expr(expr: &DeBruijnExpr, params: &mut Vec<Identifier>) -> Expr {
+    match expr {
+        DeBruijnExpr::Bound { index, name } => {
+            match params.len().checked_sub(index + 1) {
+                Some(i) => expr::v(params[i].clone()),
+                // 束縛するラムダ抽象が式の外側にある場合は元の名前のままにする
+                None => expr::v(name.clone()),
+            }
+        }
+
+        DeBruijnExpr::Free(id) => expr::v(id.clone()),
+
+        DeBruijnExpr::Symbol(id) => expr::s(id.clone()),
+
+        DeBruijnExpr::Apply { lhs, rhs } => expr::a(to_expr(lhs, params), to_expr(rhs, params)),
+
+        DeBruijnExpr::Lambda { param, body } => {
+            // body の中から参照される名前と衝突しない名前を選ぶ
+            let mut used = BoundVars::new();
+            used_names(body, 1, params, &mut used);
+            let param = if used.contains(param.clone()) {
+                param.rename(&used)
+            } else {
+                param.clone()
+            };
+
+            params.push(param.clone());
+            let body = to_expr(body, params);
+            params.pop();
+            expr::l(param, body)
+        }
+    }
+}
+
+/// depth 個のラムダ抽象の内側にある expr から、外側の名前として参照される識別子を集める
+fn used_names(expr: &DeBruijnExpr, depth: usize, params: &[Identifier], used: &mut BoundVars) {
+    match expr {
+        DeBruijnExpr::Bound { index, name } if *index >= depth => {
+            match (params.len() + depth).checked_sub(index + 1) {
+                Some(i) => used.insert(params[i].clone()),
+                None => used.insert(name.clone()),
+            }
+        }
+        DeBruijnExpr::Bound { .. } | DeBruijnExpr::Symbol(_) => {}
+        DeBruijnExpr::Free(id) => used.insert(id.clone()),
+        DeBruijnExpr::Apply { lhs, rhs } => {
+            used_names(lhs, depth, params, used);
+            used_names(rhs, depth, params, used);
+        }
+        DeBruijnExpr::Lambda { body, .. } => used_names(body, depth + 1, params, used),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_de_bruijn_from_expr() {
+        // ^x.^y.`x`yz
+        let e = expr::l("x", expr::l("y", expr::a("x", expr::a("y", "z"))));
+        let d = DeBruijnExpr::from(&e);
+
+        assert_eq!(
+            d,
+            DeBruijnExpr::Lambda {
+                param: "x".into(),
+                body: Box::new(DeBruijnExpr::Lambda {
+                    param: "y".into(),
+                    body: Box::new(DeBruijnExpr::Apply {
+                        lhs: Box::new(DeBruijnExpr::Bound {
+                            index: 1,
+                            name: "x".into()
+                        }),
+                        rhs: Box::new(DeBruijnExpr::Apply {
+                            lhs: Box::new(DeBruijnExpr::Bound {
+                                index: 0,
+                                name: "y".into()
+                            }),
+                            rhs: Box::new(DeBruijnExpr::Free("z".into())),
+                        }),
+                    }),
+                }),
+            }
+        );
+
+        // 元の名前を保持しているので、元の式に戻せる
+        assert_eq!(Expr::from(&d), e);
+
+        // ^x.^x.x の x は内側のラムダ抽象に束縛される
+        let e = expr::l("x", expr::l("x", "x"));
+        assert_eq!(
+            DeBruijnExpr::from(&e),
+            DeBruijnExpr::from(expr::l("a", expr::l("b", "b")))
+        );
+        assert_eq!(Expr::from(DeBruijnExpr::from(&e)), e);
+    }
+
+    #[test]
+    fn test_de_bruijn_eq() {
+        // α同値な式は等しい
+        assert_eq!(
+            DeBruijnExpr::from(expr::l("x", "x")),
+            DeBruijnExpr::from(expr::l("y", "y"))
+        );
+
+        // 自由変数は名前で区別される
+        assert_ne!(
+            DeBruijnExpr::from(expr::l("x", "y")),
+            DeBruijnExpr::from(expr::l("x", "z"))
+        );
+    }
+
+    #[test]
+    fn test_de_bruijn_beta_reduce() {
+        // `^x.^y.`xy y => ^Y.`yY (自由変数の y を捕獲しないよう束縛変数をリネームする)
+        let e = expr::a(expr::l("x", expr::l("y", expr::a("x", "y"))), "y");
+        let reduced = DeBruijnExpr::from(&e).beta_reduce().unwrap();
+
+        assert_eq!(reduced, DeBruijnExpr::from(expr::l("a", expr::a("y", "a"))));
+        assert_eq!(Expr::from(reduced), expr::l("Y", expr::a("y", "Y")));
+
+        // `^x.^y.x ^z.`zy => ^y.^z.`zY ... とはならず、外側の y を参照したまま
+        let e = expr::a(
+            expr::l("x", expr::l("y", "x")),
+            expr::l("z", expr::a("z", "y")),
+        );
+        let reduced = DeBruijnExpr::from(&e).beta_reduce().unwrap();
+        assert_eq!(
+            Expr::from(reduced),
+            expr::l("Y", expr::l("z", expr::a("z", "y")))
+        );
+
+        assert_eq!(DeBruijnExpr::from(expr::a("f", "x")).beta_reduce(), None);
+    }
+
+    #[test]
+    fn test_de_bruijn_shift() {
+        // ^x.`xy の y を外側のラムダ抽象に束縛された変数とみなしてずらす
+        let d = DeBruijnExpr::Lambda {
+            param: "x".into(),
+            body: Box::new(DeBruijnExpr::Apply {
+                lhs: Box::new(DeBruijnExpr::Bound {
+                    index: 0,
+                    name: "x".into(),
+                }),
+                rhs: Box::new(DeBruijnExpr::Bound {
+                    index: 1,
+                    name: "y".into(),
+                }),
+            }),
+        };
+
+        let shifted = d.shift(2, 0).unwrap();
+        match &shifted {
+            DeBruijnExpr::Lambda { body, .. } => match body.as_ref() {
+                DeBruijnExpr::Apply { lhs, rhs } => {
+                    assert!(matches!(lhs.as_ref(), DeBruijnExpr::Bound { index: 0, .. }));
+                    assert!(matches!(rhs.as_ref(), DeBruijnExpr::Bound { index: 3, .. }));
+                }
+                _ => panic!("unexpected expr"),
+            },
+            _ => panic!("unexpected expr"),
+        }
+        assert_eq!(shifted.shift(-2, 0), Some(d.clone()));
+
+        // 外側で束縛されている y の index 1 を 2 減らすと負になる
+        assert_eq!(d.shift(-2, 0), None);
+        // cutoff より小さい index はずらさない
+        assert_eq!(d.shift(-2, 2), Some(d));
+    }
+}
@@ -0,0 +1,84 @@
+use crate::expr::{Expr, FreeVars};
+
+impl Expr {
+    /// 式全体が η 簡約基 ^x.`Mx (M は x を自由変数に含まない) であれば M を返す
+    pub fn eta_reduce(&self) -> Option<&Expr> {
+        match self {
+            Expr::Lambda { param, body } => match body.as_ref() {
+                Expr::Apply { lhs, rhs } => match rhs.as_ref() {
+                    Expr::Variable(id)
+                        if id == param && !FreeVars::from(lhs.as_ref()).contains(param) =>
+                    {
+                        Some(lhs)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// ラムダ抽象の内側も含めて、η 簡約基がなくなるまで η 簡約した式を返す
+    pub fn eta_normalize(self) -> Expr {
+        match self {
+            Expr::Apply { lhs, rhs } => Expr::Apply {
+                lhs: Box::new(lhs.eta_normalize()),
+                rhs: Box::new(rhs.eta_normalize()),
+            },
+
+            // 内側から簡約することで、^x.^y.``fxy のように入れ子になった簡約基もまとめて簡約する
+            Expr::Lambda { param, body } => {
+                let lambda = Expr::Lambda {
+                    param,
+                    body: Box::new(body.eta_normalize()),
+                };
+                match lambda.eta_reduce() {
+                    Some(reduced) => reduced.clone(),
+                    None => lambda,
+                }
+            }
+
+            expr => expr,
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use crate::expr;
+
+    #[test]
+    fn test_eta_reduce() {
+        // ^x.`fx => f
+        let e = expr::l("x", expr::a("f", "x"));
+        assert_eq!(e.eta_reduce(), Some(&expr::v("f")));
+
+        // ^x.`xx は x が関数側にも現れるので η 簡約できない
+        let e = expr::l("x", expr::a("x", "x"));
+        assert_eq!(e.eta_reduce(), None);
+
+        // ^x.`fy は引数が束縛変数ではないので η 簡約できない
+        let e = expr::l("x", expr::a("f", "y"));
+        assert_eq!(e.eta_reduce(), None);
+
+        assert_eq!(expr::a("f", "x").eta_reduce(), None);
+    }
+
+    #[test]
+    fn test_eta_normalize() {
+        // ^x.^y.``fxy => f
+        let e = expr::l("x", expr::l("y", expr::a(expr::a("f", "x"), "y")));
+        assert_eq!(e.eta_normalize(), expr::v("f"));
+
+        // ^x.`x^y.`fy => ^x.`xf
+        let e = expr::l("x", expr::a("x", expr::l("y", expr::a("f", "y"))));
+        assert_eq!(e.eta_normalize(), expr::l("x", expr::a("x", "f")));
+
+        // ^x.^y.``yxy は η 簡約できない
+        let e = expr::l("x", expr::l("y", expr::a(expr::a("y", "x"), "y")));
+        assert_eq!(e.clone().eta_normalize(), e);
+    }
+}
@@ -11,7 +11,7 @@ impl Identifier {
     }
 
     pub fn rename(&self, vars: &BoundVars) -> Self {
-        let base_name = self.0.to_uppercase();
+        let base_name = self.0.to_uppercase().replace('\'', "_");
 
         if !vars.contains(base_name.as_str()) {
             return Self(base_name);
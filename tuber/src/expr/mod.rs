@@ -1,11 +1,16 @@
+mod alpha_eq;
+mod alpha_hash;
 mod bound_vars;
 mod breakdown;
+mod de_bruijn;
+mod eta;
 mod expression;
 mod free_vars;
 mod identifier;
 mod path;
 mod substitute;
 
+pub use de_bruijn::DeBruijnExpr;
 pub use expression::{a, l, s, v, Expr};
 pub use free_vars::FreeVars;
 pub use identifier::Identifier;
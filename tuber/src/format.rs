@@ -1,9 +1,11 @@
+mod blc;
 mod compact;
 mod ecmascript;
 mod formed;
 mod lazy_k;
 mod tag;
 
+pub use blc::format as blc_format;
 pub use ecmascript::format as ecmascript_format;
 pub use formed::Formed;
 pub use lazy_k::format as lazy_k_format;
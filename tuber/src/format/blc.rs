@@ -0,0 +1,3 @@
+mod expression;
+
+pub use expression::format;
@@ -0,0 +1,63 @@
+use super::super::formed::Formed;
+use super::super::tag::Tag;
+use crate::expr::Expr;
+use crate::to_string::{DisplayStyle, Format};
+
+pub fn format(expr: &Expr) -> Formed {
+    let mut formed = Formed {
+        expr: String::new(),
+        mapping: Vec::new(),
+    };
+    tokenize(expr, &Tag::new(), &mut formed);
+    formed
+}
+
+fn push(formed: &mut Formed, str: &str, tag: Tag) {
+    formed.expr.push_str(str);
+    formed.mapping.append(&mut vec![tag; str.chars().count()]);
+}
+
+/// lazy_k と同じく、関数適用の 01 には引数の位置を、それ以外には関数の位置を表すタグをつける
+fn tokenize(expr: &Expr, tag: &Tag, formed: &mut Formed) {
+    match expr {
+        Expr::Apply { .. } => {
+            let (callee, args) = expr.unapply();
+            assert!(!args.is_empty());
+
+            for index in (0..args.len()).rev() {
+                push(formed, "01", tag.push(index + 1));
+            }
+
+            tokenize(callee, tag, formed);
+
+            for (index, arg) in args.into_iter().enumerate() {
+                tokenize(arg, &tag.push(index + 1), formed);
+            }
+        }
+
+        // ラムダ抽象の内側は簡約しないので、全体に同じタグをつける
+        _ => push(formed, &expr.format(&DisplayStyle::Blc), tag.push(0)),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr::{self, Path};
+
+    #[test]
+    fn test_format() {
+        let e = expr::a(expr::a("k", ":a"), expr::l("x", "x"));
+        let formed = format(&e);
+
+        assert_eq!(formed.expr, "0101[k][:a]0010");
+        assert_eq!(formed.mapping.len(), formed.expr.chars().count());
+
+        // ``k:a の範囲
+        assert_eq!(Path::Callee(1).range(&formed.mapping), Some(2..11));
+        // 式全体の範囲
+        assert_eq!(Path::Callee(2).range(&formed.mapping), Some(0..15));
+    }
+}
@@ -0,0 +1,223 @@
+//! Lazy K のプログラムを実行するための入出力
+//!
+//! 入力はバイト列を Church 数のリストに変換してプログラムに与え、
+//! プログラムが返したリストを先頭から順に簡約して 256 が現れるまでバイト列に変換する
+//! リストは context/default.rs と同じく CONS, CAR, CDR で組み立て、分解する
+
+use crate::calc::{Eval, Strategy};
+use crate::context::Context;
+use crate::error::Error;
+use crate::expr::{self, Expr};
+
+/// 入出力の終端を表す数
+const EOF: usize = 256;
+
+/// Lazy K のプログラムに stdin を与えて実行し、出力されたバイト列を返す
+///
+/// 出力が終わらないプログラムは停止しないので、上限を設けたい場合は Output を使う
+pub fn run_lazy_k(program: Expr, stdin: &[u8]) -> Result<Vec<u8>, Error> {
+    Output::new(Context::default(), program, stdin).collect()
+}
+
+/// n を Church 数 ^f.^x.`f`f...`fx に変換する
+pub fn church_numeral(n: usize) -> Expr {
+    let body = (0..n).fold(expr::v("x"), |body, _| expr::a("f", body));
+    expr::l("f", expr::l("x", body))
+}
+
+/// バイト列を Church 数のリストに変換する
+///
+/// リストの末尾には 256 が無限に続く
+pub fn encode_input(stdin: &[u8]) -> Expr {
+    // `(^x.`xx)(^s.``CONS 256 `ss)
+    let eof = expr::a(
+        expr::l("x", expr::a("x", "x")),
+        expr::l(
+            "s",
+            expr::a(expr::a("CONS", church_numeral(EOF)), expr::a("s", "s")),
+        ),
+    );
+
+    stdin.iter().rev().fold(eof, |list, byte| {
+        expr::a(expr::a("CONS", church_numeral(*byte as usize)), list)
+    })
+}
+
+/// Lazy K のプログラムが出力するバイト列
+///
+/// リストは必要になった分だけ簡約されるので、出力が終わらないプログラムでも先頭から順に取り出せる
+#[derive(Clone, Debug)]
+pub struct Output {
+    context: Context,
+
+    /// まだ取り出していない出力のリスト
+    list: Option<Expr>,
+
+    /// これまでに簡約したステップ数
+    step: usize,
+    step_limit: Option<usize>,
+}
+
+impl Output {
+    pub fn new(context: Context, program: Expr, stdin: &[u8]) -> Self {
+        Self {
+            context,
+            list: Some(expr::a(program, encode_input(stdin))),
+            step: 0,
+            step_limit: None,
+        }
+    }
+
+    /// 出力を取り出すのに要する簡約のステップ数の合計に上限を設ける
+    ///
+    /// 上限に達すると Error::StepLimitExceeded を返す
+    pub fn with_step_limit(self, step_limit: usize) -> Self {
+        Self {
+            step_limit: Some(step_limit),
+            ..self
+        }
+    }
+
+    /// これまでに簡約したステップ数を返す
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    /// リストの先頭の Church 数を取り出し、出力のリストを1つ進める
+    fn next_number(&mut self) -> Result<Option<usize>, Error> {
+        let list = match self.list.take() {
+            Some(list) => list,
+            None => return Ok(None),
+        };
+
+        // 先頭と残りの両方で同じ簡約を繰り返さないよう、先にリストを弱頭部正規形まで簡約しておく
+        let list = self.reduce(list, Strategy::CallByName)?;
+
+        let head = expr::a(
+            expr::a(expr::a("CAR", list.clone()), expr::s("f")),
+            expr::s("x"),
+        );
+        let head = self.reduce(head, Strategy::NormalOrder)?;
+        let n = decode_numeral(&head).ok_or(Error::NotANumeral(head))?;
+
+        if n < EOF {
+            self.list = Some(expr::a("CDR", list));
+            Ok(Some(n))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// 簡約できなくなるまで式を簡約する
+    fn reduce(&mut self, expr: Expr, strategy: Strategy) -> Result<Expr, Error> {
+        let mut eval = Eval::new(self.context.clone(), expr.clone(), strategy);
+        if let Some(step_limit) = self.step_limit {
+            eval = eval.with_step_limit(step_limit.saturating_sub(self.step));
+        }
+
+        let mut result = expr;
+        for step in eval {
+            let step = step.map_err(|err| match (err, self.step_limit) {
+                (Error::StepLimitExceeded(_), Some(step_limit)) => {
+                    Error::StepLimitExceeded(step_limit)
+                }
+                (err, _) => err,
+            })?;
+            self.step += 1;
+            result = step.expr;
+        }
+        Ok(result)
+    }
+}
+
+impl Iterator for Output {
+    type Item = Result<u8, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.next_number() {
+            Ok(Some(n)) => Some(Ok(n as u8)),
+            Ok(None) => None,
+            Err(err) => {
+                self.list = None;
+                Some(Err(err))
+            }
+        }
+    }
+}
+
+/// ``:f`:f...`:f:x の形の式から :f が適用された回数を数える
+fn decode_numeral(expr: &Expr) -> Option<usize> {
+    let mut n = 0;
+    let mut expr = expr;
+    loop {
+        match expr {
+            Expr::Symbol(id) if id.as_ref() == "x" => return Some(n),
+            Expr::Apply { lhs, rhs } if **lhs == expr::s("f") => {
+                n += 1;
+                expr = rhs;
+            }
+            _ => return None,
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_lazy_k_program;
+
+    #[test]
+    fn test_run_lazy_k() {
+        // 入力をそのまま出力する
+        let program = parse_lazy_k_program("I").unwrap();
+        assert_eq!(run_lazy_k(program, b"Hello").unwrap(), b"Hello");
+
+        let program = parse_lazy_k_program("SKK").unwrap();
+        assert_eq!(run_lazy_k(program, b"").unwrap(), b"");
+
+        // 入力を無視して 256 を返し続ける
+        let program = parse_lazy_k_program("K(K(SII(SII(S(S(KS)K)I))))").unwrap();
+        assert_eq!(run_lazy_k(program, b"ignored").unwrap(), b"");
+
+        // 入力の先頭を読み飛ばす
+        let program = expr::a(expr::a("s", expr::a("k", "CDR")), "i");
+        assert_eq!(run_lazy_k(program, b"abc").unwrap(), b"bc");
+    }
+
+    #[test]
+    fn test_output_incremental() {
+        // 'A' を無限に出力する
+        let program = expr::a(
+            "k",
+            expr::a(
+                expr::l("x", expr::a("x", "x")),
+                expr::l(
+                    "s",
+                    expr::a(expr::a("CONS", church_numeral(65)), expr::a("s", "s")),
+                ),
+            ),
+        );
+
+        let output = Output::new(Context::default(), program.clone(), b"");
+        let bytes: Result<Vec<u8>, Error> = output.take(3).collect();
+        assert_eq!(bytes.unwrap(), b"AAA");
+
+        let mut output = Output::new(Context::default(), program, b"").with_step_limit(100);
+        assert_eq!(
+            output.find(|byte| byte.is_err()),
+            Some(Err(Error::StepLimitExceeded(100)))
+        );
+        assert_eq!(output.next(), None);
+    }
+
+    #[test]
+    fn test_not_a_numeral() {
+        let program = expr::a("k", expr::a(expr::a("CONS", "k"), "i"));
+        assert_eq!(
+            run_lazy_k(program, b""),
+            Err(Error::NotANumeral(expr::s("f")))
+        );
+    }
+}
@@ -1,18 +1,27 @@
 mod calc;
 mod context;
 mod engine;
+mod error;
 mod expr;
 mod format;
 mod func;
+pub mod io;
 mod parser;
 mod style;
 mod to_string;
 
-pub use calc::{Eval, EvalStep, ReduceResult, Reducer};
+pub use calc::{
+    blc_encode, infer_type, metrics, Basis, Equivalence, Eval, EvalStep, FoldBack, GraphEval,
+    History, Metrics, StepKind, Strategy, Type,
+};
 pub use context::Context;
-pub use engine::{Command, Engine, RunResult};
-pub use expr::{Expr, Identifier, Path};
-pub use format::{ecmascript_format, lazy_k_format, Formed, Tag};
+pub use engine::{Command, Engine, Outcome, RunResult};
+pub use error::Error;
+pub use expr::{DeBruijnExpr, Expr, Identifier, Path};
+pub use format::{blc_format, ecmascript_format, lazy_k_format, Formed, Tag};
 pub use func::Func;
-pub use parser::{parse_command, parse_expr, parse_update_or_delete};
-pub use to_string::{DisplayStyle, Format};
+pub use parser::{
+    parse_blc, parse_command, parse_expr, parse_jot, parse_lazy_k_program, parse_script,
+    parse_unlambda, parse_update_or_delete, ParseError, Position, Span,
+};
+pub use to_string::{to_jot, to_unlambda, DisplayStyle, Format, FormatOptions};
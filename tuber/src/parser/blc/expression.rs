@@ -0,0 +1,144 @@
+use super::super::identifier::identifier;
+use crate::expr::{DeBruijnExpr, Identifier};
+use combine::parser::char::{char, spaces, string};
+use combine::parser::choice::choice;
+use combine::{attempt, many, optional, parser, ParseError, Parser, Stream};
+
+/// 深さに応じて束縛変数につける名前
+const PARAMS: [&str; 6] = ["x", "y", "z", "w", "v", "u"];
+
+/// ビット列を構文解析する
+///
+/// ラムダ抽象の引数と束縛変数の名前は空のままにしておき、bind でつける
+pub fn expr<Input>() -> impl Parser<Input, Output = DeBruijnExpr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    expr_()
+}
+
+parser! {
+    fn expr_[Input]()(Input) -> DeBruijnExpr
+    where [
+        Input: Stream<Token = char>,
+        Input::Error: ParseError<char, Input::Range, Input::Position>,
+    ]
+    {
+        spaces().with(choice((
+            attempt(lambda()),
+            apply(),
+            var(),
+            free(),
+        )))
+    }
+}
+
+fn lambda<Input>() -> impl Parser<Input, Output = DeBruijnExpr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    string("00").with(expr()).map(|body| DeBruijnExpr::Lambda {
+        param: Identifier::from(""),
+        body: Box::new(body),
+    })
+}
+
+fn apply<Input>() -> impl Parser<Input, Output = DeBruijnExpr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    string("01")
+        .with(expr())
+        .and(expr())
+        .map(|(lhs, rhs)| DeBruijnExpr::Apply {
+            lhs: Box::new(lhs),
+            rhs: Box::new(rhs),
+        })
+}
+
+fn var<Input>() -> impl Parser<Input, Output = DeBruijnExpr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    char('1')
+        .with(many(char('1')))
+        .skip(char('0'))
+        .map(|ones: String| DeBruijnExpr::Bound {
+            index: ones.len(),
+            name: Identifier::from(""),
+        })
+}
+
+/// BLC で表現できない自由変数とシンボルは [x] や [:a] のように角括弧で囲む
+fn free<Input>() -> impl Parser<Input, Output = DeBruijnExpr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    char('[')
+        .with(optional(char(':')))
+        .and(identifier())
+        .skip(spaces().with(char(']')))
+        .map(|(colon, id)| match colon {
+            Some(_) => DeBruijnExpr::Symbol(id),
+            None => DeBruijnExpr::Free(id),
+        })
+}
+
+/// depth 個のラムダ抽象の内側にある式の束縛変数に名前をつける
+///
+/// 束縛しているラムダ抽象がない index があれば、その index を返す
+pub fn bind(expr: DeBruijnExpr, depth: usize) -> Result<DeBruijnExpr, usize> {
+    match expr {
+        DeBruijnExpr::Bound { index, .. } if index < depth => Ok(DeBruijnExpr::Bound {
+            index,
+            name: param_name(depth - index - 1),
+        }),
+        DeBruijnExpr::Bound { index, .. } => Err(index),
+        DeBruijnExpr::Apply { lhs, rhs } => Ok(DeBruijnExpr::Apply {
+            lhs: Box::new(bind(*lhs, depth)?),
+            rhs: Box::new(bind(*rhs, depth)?),
+        }),
+        DeBruijnExpr::Lambda { body, .. } => Ok(DeBruijnExpr::Lambda {
+            param: param_name(depth),
+            body: Box::new(bind(*body, depth + 1)?),
+        }),
+        expr => Ok(expr),
+    }
+}
+
+fn param_name(depth: usize) -> Identifier {
+    match PARAMS.get(depth) {
+        Some(name) => Identifier::from(*name),
+        None => Identifier::from(format!("X{}", depth)),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combine::EasyParser;
+
+    #[test]
+    fn test_expr() {
+        assert!(matches!(
+            expr().easy_parse("1110"),
+            Ok((DeBruijnExpr::Bound { index: 2, .. }, ""))
+        ));
+        assert!(matches!(
+            expr().easy_parse("[:a]"),
+            Ok((DeBruijnExpr::Symbol(_), ""))
+        ));
+        assert!(matches!(
+            expr().easy_parse("[i]"),
+            Ok((DeBruijnExpr::Free(_), ""))
+        ));
+        assert!(expr().easy_parse("11").is_err());
+    }
+}
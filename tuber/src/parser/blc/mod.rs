@@ -0,0 +1,67 @@
+mod expression;
+
+use crate::expr::Expr;
+use anyhow::{anyhow, Result};
+use combine::EasyParser;
+pub use expression::expr;
+
+/// Binary Lambda Calculus のビット列を式に変換する
+///
+/// 束縛変数には束縛しているラムダ抽象の深さに応じた名前をつける
+pub fn parse_expr(s: &str) -> Result<Expr> {
+    let (expr, rest) = expr().easy_parse(s).map_err(|e| anyhow!("{}", e))?;
+
+    if !rest.is_empty() {
+        return Err(anyhow!("unexpected token: {}", rest));
+    }
+
+    expression::bind(expr, 0)
+        .map(Expr::from)
+        .map_err(|index| anyhow!("unbound de Bruijn index: {}", index))
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_parse_expr() {
+        assert_eq!(parse_expr("0010").unwrap(), expr::l("x", "x"));
+        assert_eq!(
+            parse_expr("0000110").unwrap(),
+            expr::l("x", expr::l("y", "x"))
+        );
+        assert_eq!(
+            parse_expr("00000001011110100111010").unwrap(),
+            expr::l(
+                "x",
+                expr::l(
+                    "y",
+                    expr::l("z", expr::a(expr::a("x", "z"), expr::a("y", "z")))
+                )
+            )
+        );
+
+        // 空白を挟んでもよい
+        assert_eq!(
+            parse_expr("01 0010 [:a]").unwrap(),
+            expr::a(expr::l("x", "x"), ":a")
+        );
+
+        // 自由変数の名前と衝突する束縛変数はリネームする
+        assert_eq!(
+            parse_expr("00 01 10 [x]").unwrap(),
+            expr::l("X", expr::a("X", "x"))
+        );
+
+        // 束縛されていない index はエラー
+        assert!(parse_expr("00110").is_err());
+
+        // ビット列が余ったり足りなかったりするとエラー
+        assert!(parse_expr("00100").is_err());
+        assert!(parse_expr("0100").is_err());
+    }
+}
@@ -65,7 +65,7 @@ parser! {
         spaces()
             .with(callable())
             .and(
-                many1(spaces().with(args()))
+                many1(attempt(spaces().with(args())))
             )
             .map(|(mut e, argss)| {
                 let _: Vec<Vec<Expr>> = argss;
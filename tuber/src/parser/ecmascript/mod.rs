@@ -3,33 +3,44 @@ mod expression;
 
 use crate::engine::Command;
 use crate::expr::Expr;
+use crate::parser::ParseError;
+use crate::to_string::DisplayStyle;
 use anyhow::{anyhow, Result};
 use combine::EasyParser;
 pub use command::{command, update};
 pub use expression::expr;
 
+/// 構文解析に失敗したときは ParseError を返す
 pub fn parse_expr(s: &str) -> Result<Expr> {
-    let (expr, rest) = expr().easy_parse(s).map_err(|e| anyhow!("{}", e))?;
+    let (expr, rest) = expr()
+        .easy_parse(s)
+        .map_err(|e| ParseError::new(s, e, DisplayStyle::EcmaScript))?;
 
     if rest.is_empty() {
         Ok(expr)
     } else {
-        Err(anyhow!("unexpected token: {}", rest))
+        Err(ParseError::trailing(s, rest, DisplayStyle::EcmaScript).into())
     }
 }
 
+/// 構文解析に失敗したときは ParseError を返す
 pub fn parse_command(s: &str) -> Result<Command> {
-    let (command, rest) = command().easy_parse(s).map_err(|e| anyhow!("{}", e))?;
+    let (command, rest) = command()
+        .easy_parse(s)
+        .map_err(|e| ParseError::new(s, e, DisplayStyle::EcmaScript))?;
 
     if rest.is_empty() {
         Ok(command)
     } else {
-        Err(anyhow!("unexpected token: {}", rest))
+        Err(ParseError::trailing(s, rest, DisplayStyle::EcmaScript).into())
     }
 }
 
+/// 構文解析に失敗したときは ParseError を返す
 pub fn parse_update_or_delete(s: &str) -> Result<Command> {
-    let (command, rest) = update().easy_parse(s).map_err(|e| anyhow!("{}", e))?;
+    let (command, rest) = update()
+        .easy_parse(s)
+        .map_err(|e| ParseError::new(s, e, DisplayStyle::EcmaScript))?;
 
     if rest.is_empty() {
         match &command {
@@ -38,6 +49,6 @@ pub fn parse_update_or_delete(s: &str) -> Result<Command> {
             _ => Err(anyhow!("unexpected command: {}", command)),
         }
     } else {
-        Err(anyhow!("unexpected token: {}", rest))
+        Err(ParseError::trailing(s, rest, DisplayStyle::EcmaScript).into())
     }
 }
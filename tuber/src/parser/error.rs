@@ -0,0 +1,152 @@
+use crate::to_string::DisplayStyle;
+use combine::easy::{Error, Errors};
+use combine::stream::PointerOffset;
+use std::fmt::Display;
+
+/// 構文解析に失敗した位置と、その位置で期待していたトークン
+///
+/// offset は入力の先頭からの文字数、line と col は 1 から数えた行と列を表す
+/// 複数のスタイルで解析を試みた場合は、最も先まで読み進めたスタイルのエラーを残し、
+/// 同じ位置で失敗したスタイルがあれば expected と style_tried をまとめる
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub offset: usize,
+    pub line: usize,
+    pub col: usize,
+    pub expected: Vec<String>,
+    pub style_tried: Vec<DisplayStyle>,
+}
+
+impl ParseError {
+    /// combine の easy_parse が返したエラーを変換する
+    pub fn new(
+        input: &str,
+        errors: Errors<char, &str, PointerOffset<str>>,
+        style: DisplayStyle,
+    ) -> Self {
+        let mut expected = Vec::new();
+        for error in errors.errors {
+            if let Error::Expected(info) = error {
+                let info = info.to_string();
+                if !expected.contains(&info) {
+                    expected.push(info);
+                }
+            }
+        }
+
+        let bytes = errors.position.translate_position(input);
+        Self::at(input, bytes, expected, style)
+    }
+
+    /// 入力の途中で解析が終わり、残りの rest を読み残したときのエラー
+    pub fn trailing(input: &str, rest: &str, style: DisplayStyle) -> Self {
+        let bytes = input.len() - rest.len();
+        Self::at(input, bytes, vec!["end of input".to_string()], style)
+    }
+
+    fn at(input: &str, bytes: usize, expected: Vec<String>, style: DisplayStyle) -> Self {
+        let consumed = &input[..bytes];
+
+        let offset = consumed.chars().count();
+        let line = consumed.matches('\n').count() + 1;
+        let col = match consumed.rfind('\n') {
+            Some(newline) => consumed[newline + 1..].chars().count() + 1,
+            None => offset + 1,
+        };
+
+        Self {
+            offset,
+            line,
+            col,
+            expected,
+            style_tried: vec![style],
+        }
+    }
+
+    /// 別のスタイルで解析したときのエラーとまとめる
+    pub fn merge(self, other: Self) -> Self {
+        match self.offset.cmp(&other.offset) {
+            std::cmp::Ordering::Greater => self,
+            std::cmp::Ordering::Less => other,
+            std::cmp::Ordering::Equal => {
+                let mut merged = self;
+                for info in other.expected {
+                    if !merged.expected.contains(&info) {
+                        merged.expected.push(info);
+                    }
+                }
+                merged.style_tried.extend(other.style_tried);
+                merged
+            }
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Parse error at line {}, column {}", self.line, self.col)?;
+        if !self.expected.is_empty() {
+            write!(f, ": expected {}", self.expected.join(" or "))?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combine::parser::char::{char, spaces, string};
+    use combine::{EasyParser, Parser};
+
+    fn parse(input: &str) -> ParseError {
+        let err = spaces()
+            .with(string("λx"))
+            .skip(char('.'))
+            .easy_parse(input)
+            .unwrap_err();
+        ParseError::new(input, err, DisplayStyle::LazyK)
+    }
+
+    #[test]
+    fn test_new() {
+        let err = parse("\n λx,");
+        assert_eq!(err.offset, 4);
+        assert_eq!((err.line, err.col), (2, 4));
+        assert_eq!(err.expected, vec!["`.`"]);
+        assert_eq!(
+            err.to_string(),
+            "Parse error at line 2, column 4: expected `.`"
+        );
+    }
+
+    #[test]
+    fn test_merge() {
+        let lazy_k = parse("λx,");
+        let ecmascript = ParseError {
+            offset: 2,
+            line: 1,
+            col: 3,
+            expected: vec!["`=>`".to_string()],
+            style_tried: vec![DisplayStyle::EcmaScript],
+        };
+
+        let merged = ecmascript.clone().merge(lazy_k.clone());
+        assert_eq!(merged.expected, vec!["`=>`", "`.`"]);
+        assert_eq!(
+            merged.style_tried,
+            vec![DisplayStyle::EcmaScript, DisplayStyle::LazyK]
+        );
+
+        // 先まで読み進めたスタイルのエラーを残す
+        let ecmascript = ParseError {
+            offset: 1,
+            col: 2,
+            ..ecmascript
+        };
+        assert_eq!(ecmascript.merge(lazy_k.clone()), lazy_k);
+    }
+}
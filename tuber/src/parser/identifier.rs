@@ -1,6 +1,6 @@
 use combine::parser::char::{char, digit, lower, spaces, upper};
 use combine::parser::choice::choice;
-use combine::{many1, ParseError, Parser, Stream};
+use combine::{many, many1, ParseError, Parser, Stream};
 
 use crate::expr::Identifier;
 
@@ -17,9 +17,10 @@ where
     Input: Stream<Token = char>,
     Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
 {
-    lower().map(|c| {
-        let mut buffer = [0; 4];
-        let s: &str = c.encode_utf8(&mut buffer);
+    // s' や b' のように ' を後ろに付けてもよい
+    (lower(), many(char('\''))).map(|(c, primes): (char, String)| {
+        let mut s = c.to_string();
+        s.push_str(&primes);
         s.into()
     })
 }
@@ -54,6 +55,8 @@ mod tests {
         assert_eq!(identifier().easy_parse("ABCabc"), Ok(("ABC".into(), "abc")));
         assert_eq!(identifier().easy_parse("A_B_C"), Ok(("A_B_C".into(), "")));
         assert_eq!(identifier().easy_parse("42"), Ok(("42".into(), "")));
+        assert_eq!(identifier().easy_parse("s'x"), Ok(("s'".into(), "x")));
+        assert_eq!(identifier().easy_parse("b''"), Ok(("b''".into(), "")));
 
         assert!(identifier().easy_parse(":abc").is_err());
         assert!(identifier().easy_parse("^abc").is_err());
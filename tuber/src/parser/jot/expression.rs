@@ -0,0 +1,46 @@
+use crate::expr::{self, Expr};
+use combine::parser::char::{char, spaces};
+use combine::parser::choice::choice;
+use combine::{many, ParseError, Parser, Stream};
+
+/// Jot のプログラムを構文解析する
+pub fn expr<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    spaces()
+        .with(many(choice((char('0'), char('1'))).skip(spaces())))
+        .map(|bits: String| decode(&bits))
+}
+
+/// 0 と 1 からなる文字列を左から順に読み、空のプログラムを表す i に対して次のように組み立てる
+///
+/// [F0] = ``[F]sk
+/// [F1] = `s`k[F]
+pub fn decode(bits: &str) -> Expr {
+    bits.chars().fold(expr::v("i"), |acc, bit| match bit {
+        '0' => expr::a(expr::a(acc, "s"), "k"),
+        _ => expr::a("s", expr::a("k", acc)),
+    })
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combine::EasyParser;
+
+    #[test]
+    fn test_expr() {
+        assert_eq!(
+            expr().easy_parse("10"),
+            Ok((
+                expr::a(expr::a(expr::a("s", expr::a("k", "i")), "s"), "k"),
+                ""
+            ))
+        );
+        assert_eq!(expr().easy_parse(" 1 0 "), expr().easy_parse("10"));
+    }
+}
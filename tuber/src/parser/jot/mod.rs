@@ -0,0 +1,52 @@
+mod expression;
+
+use crate::expr::Expr;
+use anyhow::{anyhow, Result};
+use combine::EasyParser;
+pub use expression::{decode, expr};
+
+/// Jot のプログラムを s, k, i からなる式に変換する
+pub fn parse_expr(s: &str) -> Result<Expr> {
+    let (expr, rest) = expr().easy_parse(s).map_err(|e| anyhow!("{}", e))?;
+
+    if rest.is_empty() {
+        Ok(expr)
+    } else {
+        Err(anyhow!("unexpected token: {}", rest))
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::{equivalence, Equivalence};
+    use crate::context::Context;
+    use crate::expr;
+
+    fn assert_equivalent(jot: &str, expected: Expr) {
+        let actual = parse_expr(jot).unwrap();
+        assert_eq!(
+            equivalence(&Context::default(), actual, expected, 1000),
+            Ok(Equivalence::Equal),
+            "{}",
+            jot
+        );
+    }
+
+    #[test]
+    fn test_parse_expr() {
+        assert_eq!(parse_expr("").unwrap(), expr::v("i"));
+        assert_eq!(parse_expr("0").unwrap(), expr::a(expr::a("i", "s"), "k"));
+        assert_eq!(parse_expr("1").unwrap(), expr::a("s", expr::a("k", "i")));
+
+        assert_equivalent("11100", expr::v("k"));
+        assert_equivalent("11111000", expr::v("s"));
+
+        // `AB は 1 の後に A と B を並べる
+        assert_equivalent("1 11111000 11100", expr::a("s", "k"));
+
+        assert!(parse_expr("2").is_err());
+    }
+}
@@ -1,6 +1,6 @@
-use combine::parser::char::{char, digit, spaces, string};
+use combine::parser::char::{char, digit, space, spaces, string};
 use combine::parser::choice::choice;
-use combine::{attempt, count_min_max, eof, many1, parser, ParseError, Parser, Stream};
+use combine::{attempt, count_min_max, eof, many1, parser, skip_many1, ParseError, Parser, Stream};
 
 use super::super::identifier::identifier;
 use super::expression::expr;
@@ -17,10 +17,15 @@ where
 {
     choice((
         attempt(update()),
+        attempt(equiv()),
+        attempt(bit_length()),
+        attempt(type_of()),
         eval(),
         attempt(eval_head()),
         attempt(eval_tail()),
         eval_last(),
+        attempt(unlambda_compare()),
+        attempt(unlambda_context()),
         attempt(unlambda()),
         attempt(query()),
         global(),
@@ -91,6 +96,47 @@ where
     expr().map(Command::Eval)
 }
 
+fn equiv<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    expr()
+        .skip(spaces().with(string("==")))
+        .and(expr())
+        .map(|(lhs, rhs)| Command::Equiv(lhs, rhs))
+}
+
+fn bit_length<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    spaces()
+        .with(string(":blc"))
+        .skip(skip_many1(space()))
+        .with(expr())
+        .map(Command::BitLength)
+}
+
+fn type_of<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    spaces()
+        .with(string(":t"))
+        .skip(skip_many1(space()))
+        .with(expr())
+        .map(Command::Type)
+}
+
 fn eval_last<Input>() -> impl Parser<Input, Output = Command>
 where
     Input: Stream<Token = char>,
@@ -161,7 +207,7 @@ where
     <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
         From<::std::num::ParseIntError>,
 {
-    let level = count_min_max(1, 4, char('~')).map(|str: String| str.len() as u8);
+    let level = count_min_max(1, 8, char('~')).map(|str: String| str.len() as u8);
 
     spaces()
         .with(level)
@@ -169,6 +215,32 @@ where
         .map(|(level, expr)| Command::Unlambda(level, expr))
 }
 
+fn unlambda_compare<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+    <Input::Error as ParseError<Input::Token, Input::Range, Input::Position>>::StreamError:
+        From<::std::num::ParseIntError>,
+{
+    spaces()
+        .with(string("~*"))
+        .with(spaces().with(expr()))
+        .map(Command::UnlambdaCompare)
+}
+
+fn unlambda_context<Input>() -> impl Parser<Input, Output = Command>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    let level = count_min_max(1, 8, char('~')).map(|str: String| str.len() as u8);
+
+    spaces()
+        .with(level)
+        .skip(spaces().with(char('?')))
+        .map(Command::UnlambdaContext)
+}
+
 // ========================================================================== //
 
 #[cfg(test)]
@@ -335,6 +407,44 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_equiv() {
+        assert_eq!(
+            equiv().easy_parse("```skk:a == `i:a"),
+            Ok((
+                Command::Equiv(
+                    expr::a(expr::a(expr::a("s", "k"), "k"), ":a"),
+                    expr::a("i", ":a")
+                ),
+                ""
+            ))
+        );
+        assert_eq!(
+            command().easy_parse("^x.x==i"),
+            Ok((Command::Equiv(expr::l("x", "x"), "i".into()), ""))
+        );
+
+        // = が1つだけなら関数定義になる
+        assert_eq!(
+            command().easy_parse("a = b"),
+            Ok((Command::Update(func::new("a", Vec::<&str>::new(), "b")), ""))
+        );
+    }
+
+    #[test]
+    fn test_bit_length() {
+        assert_eq!(
+            command().easy_parse(":blc `ki"),
+            Ok((Command::BitLength(expr::a("k", "i")), ""))
+        );
+
+        // :b はシンボル
+        assert_eq!(
+            command().easy_parse(":b"),
+            Ok((Command::Eval(expr::s("b")), ""))
+        );
+    }
+
     #[test]
     fn test_query() {
         assert_eq!(
@@ -374,6 +484,45 @@ mod tests {
             Ok((Command::Unlambda(4, expr::l("x", "x")), ""))
         );
 
-        assert!(unlambda().easy_parse("~~~~~^x.x").is_err());
+        assert_eq!(
+            unlambda().easy_parse("~~~~~^x.x"),
+            Ok((Command::Unlambda(5, expr::l("x", "x")), ""))
+        );
+
+        assert_eq!(
+            unlambda().easy_parse("~~~~~~~~^x.x"),
+            Ok((Command::Unlambda(8, expr::l("x", "x")), ""))
+        );
+
+        assert!(unlambda().easy_parse("~~~~~~~~~^x.x").is_err());
+    }
+
+    #[test]
+    fn test_unlambda_compare() {
+        assert_eq!(
+            unlambda_compare().easy_parse("~* ^x.x"),
+            Ok((Command::UnlambdaCompare(expr::l("x", "x")), ""))
+        );
+    }
+
+    #[test]
+    fn test_unlambda_context() {
+        assert_eq!(
+            unlambda_context().easy_parse("~~?"),
+            Ok((Command::UnlambdaContext(2), ""))
+        );
+
+        assert_eq!(
+            command().easy_parse("~~~~~~~~ ?"),
+            Ok((Command::UnlambdaContext(8), ""))
+        );
+    }
+
+    #[test]
+    fn test_type_of() {
+        assert_eq!(
+            command().easy_parse(":t ^x.x"),
+            Ok((Command::Type(expr::l("x", "x")), ""))
+        );
     }
 }
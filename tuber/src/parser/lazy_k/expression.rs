@@ -1,4 +1,5 @@
 use super::super::identifier::identifier;
+use super::program::{cc_expr, iota_expr};
 use crate::expr::{self, Expr, Identifier};
 use combine::parser::char::{char, spaces};
 use combine::parser::choice::choice;
@@ -27,6 +28,8 @@ parser! {
             apply(),
             lambda(),
             symbol(),
+            iota(),
+            parens(),
             var(),
         )))
     }
@@ -92,6 +95,29 @@ parser! {
     }
 }
 
+/// Lazy K の Iota 記法 *AB
+fn iota<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    char('*')
+        .with(iota_expr())
+        .and(iota_expr())
+        .map(|(lhs, rhs)| expr::a(lhs, rhs))
+}
+
+/// Lazy K の Combinator Calculus 記法 (SKI)
+///
+/// パーレンの内側は Lazy K の構文で解析する
+fn parens<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    char('(').with(cc_expr()).skip(char(')'))
+}
+
 fn params<Input>() -> impl Parser<Input, Output = Vec<Identifier>>
 where
     Input: Stream<Token = char>,
@@ -152,6 +178,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_lazy_k() {
+        assert_eq!(expr().easy_parse("*ii"), Ok((expr::a("ι", "ι"), "")));
+        assert_eq!(
+            expr().easy_parse("(SKI)"),
+            Ok((expr::a(expr::a("s", "k"), "i"), ""))
+        );
+
+        // 従来の構文と混ぜて書ける
+        assert_eq!(
+            expr().easy_parse("`(SK):a"),
+            Ok((expr::a(expr::a("s", "k"), ":a"), ""))
+        );
+    }
+
     #[test]
     fn test_lambda() {
         assert!(expr().easy_parse("^a").is_err());
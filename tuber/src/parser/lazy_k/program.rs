@@ -0,0 +1,157 @@
+use super::super::jot;
+use crate::expr::{self, Expr};
+use combine::parser::char::{char, space};
+use combine::parser::choice::choice;
+use combine::{many, many1, none_of, parser, skip_many, ParseError, Parser, Stream};
+
+/// Lazy K のプログラムを構文解析する
+///
+/// 4つの構文を混ぜて書ける
+///
+/// - Combinator Calculus: S K I と、パーレンで囲んだ左結合の並置 (SKI)
+/// - Unlambda: `AB
+/// - Iota: *AB (* の直後の i だけは ι になる)
+/// - Jot: 0 と 1 の並び
+///
+/// 空白と # から行末までのコメントは読み飛ばす、空のプログラムは i になる
+pub fn program<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    blank().with(cc_expr())
+}
+
+/// 式を並置した左結合の関数適用
+pub fn cc_expr<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    many(expr()).map(|exprs: Vec<Expr>| {
+        exprs
+            .into_iter()
+            .reduce(expr::a)
+            .unwrap_or_else(|| expr::v("i"))
+    })
+}
+
+/// * の後に続く2つの式
+pub fn iota_expr<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    choice((lexeme(char('i')).map(|_| expr::v("ι")), expr2()))
+}
+
+fn expr<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    choice((lexeme(char('i')).map(|_| expr::v("i")), expr2()))
+}
+
+parser! {
+    fn expr2[Input]()(Input) -> Expr
+    where [
+        Input: Stream<Token = char>,
+        Input::Error: ParseError<char, Input::Range, Input::Position>,
+    ]
+    {
+        choice((
+            lexeme(char('I')).map(|_| expr::v("i")),
+            lexeme(choice((char('K'), char('k')))).map(|_| expr::v("k")),
+            lexeme(choice((char('S'), char('s')))).map(|_| expr::v("s")),
+            many1(lexeme(choice((char('0'), char('1'))))).map(|bits: String| jot::decode(&bits)),
+            lexeme(char('`')).with(expr()).and(expr()).map(|(lhs, rhs)| expr::a(lhs, rhs)),
+            lexeme(char('*'))
+                .with(iota_expr())
+                .and(iota_expr())
+                .map(|(lhs, rhs)| expr::a(lhs, rhs)),
+            lexeme(char('(')).with(cc_expr()).skip(lexeme(char(')'))),
+        ))
+    }
+}
+
+fn lexeme<Input, Output>(
+    parser: impl Parser<Input, Output = Output>,
+) -> impl Parser<Input, Output = Output>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    parser.skip(blank())
+}
+
+/// 空白と # から行末までのコメント
+fn blank<Input>() -> impl Parser<Input, Output = ()>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    skip_many(choice((
+        space().map(|_| ()),
+        char('#').with(skip_many(none_of("\n".chars()))),
+    )))
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combine::EasyParser;
+
+    fn parse(s: &str) -> Expr {
+        let (expr, rest) = program().easy_parse(s).unwrap();
+        assert_eq!(rest, "");
+        expr
+    }
+
+    #[test]
+    fn test_combinator_calculus() {
+        assert_eq!(parse("SKI"), expr::a(expr::a("s", "k"), "i"));
+        assert_eq!(parse("S(KI)"), expr::a("s", expr::a("k", "i")));
+        assert_eq!(parse("s k i"), expr::a(expr::a("s", "k"), "i"));
+        assert_eq!(parse("()"), expr::v("i"));
+        assert_eq!(parse(""), expr::v("i"));
+    }
+
+    #[test]
+    fn test_unlambda() {
+        assert_eq!(parse("``skk"), expr::a(expr::a("s", "k"), "k"));
+        assert_eq!(parse("`S`KI"), expr::a("s", expr::a("k", "i")));
+    }
+
+    #[test]
+    fn test_iota() {
+        assert_eq!(parse("*ii"), expr::a("ι", "ι"));
+        assert_eq!(parse("*i*ii"), expr::a("ι", expr::a("ι", "ι")));
+
+        // * の外側の i は I
+        assert_eq!(parse("`i*ii"), expr::a("i", expr::a("ι", "ι")));
+    }
+
+    #[test]
+    fn test_jot() {
+        assert_eq!(parse("11100"), jot::decode("11100"));
+        assert_eq!(parse("`K 11100"), expr::a("k", jot::decode("11100")));
+    }
+
+    #[test]
+    fn test_mixed() {
+        let s = "
+            # K と I を並べる
+            K   # コメント
+            (`*ii 0)
+        ";
+        assert_eq!(
+            parse(s),
+            expr::a("k", expr::a(expr::a("ι", "ι"), jot::decode("0")))
+        );
+
+        assert!(program().easy_parse("(SK").is_err());
+    }
+}
@@ -1,28 +1,150 @@
+mod blc;
 mod ecmascript;
+mod error;
 mod identifier;
+mod jot;
 mod lazy_k;
+mod script;
+mod unlambda;
 mod utils;
 
 use crate::engine::Command;
 use crate::expr::Expr;
 use anyhow::Result;
+pub use blc::parse_expr as parse_blc;
 pub use ecmascript::parse_command as parse_command_with_ecmascript_style;
 pub use ecmascript::parse_expr as parse_expr_with_ecmascript_style;
 pub use ecmascript::parse_update_or_delete as parse_update_or_delete_with_ecmascript_style;
+pub use error::ParseError;
+pub use jot::parse_expr as parse_jot;
 pub use lazy_k::parse_command as parse_command_with_lazy_k_style;
 pub use lazy_k::parse_expr as parse_expr_with_lazy_k_style;
+pub use lazy_k::parse_program as parse_lazy_k_program;
 pub use lazy_k::parse_update_or_delete as parse_update_or_delete_with_lazy_k_style;
+pub use script::{parse_script, Position, Span};
+pub use unlambda::parse_expr as parse_unlambda;
 
+/// どちらのスタイルでも解析できなかったときは、両方のエラーをまとめた ParseError を返す
 pub fn parse_expr(input: &str) -> Result<Expr> {
-    parse_expr_with_ecmascript_style(input).or_else(|_err| parse_expr_with_lazy_k_style(input))
+    parse_expr_with_ecmascript_style(input)
+        .or_else(|ecmascript| {
+            parse_expr_with_lazy_k_style(input).map_err(|lazy_k| merge(ecmascript, lazy_k))
+        })
+        .or_else(|err| parse_lazy_k_program_or(input, err))
 }
 
+/// どちらのスタイルでも解析できなかったときは、両方のエラーをまとめた ParseError を返す
 pub fn parse_command(input: &str) -> Result<Command> {
     parse_command_with_ecmascript_style(input)
-        .or_else(|_err| parse_command_with_lazy_k_style(input))
+        .or_else(|ecmascript| {
+            parse_command_with_lazy_k_style(input).map_err(|lazy_k| merge(ecmascript, lazy_k))
+        })
+        .or_else(|err| parse_lazy_k_program_or(input, err).map(Command::Eval))
 }
 
+/// 2 つのスタイルのエラーが ParseError であればまとめ、そうでなければ後者を返す
+fn merge(first: anyhow::Error, second: anyhow::Error) -> anyhow::Error {
+    match (
+        first.downcast::<ParseError>(),
+        second.downcast::<ParseError>(),
+    ) {
+        (Ok(first), Ok(second)) => first.merge(second).into(),
+        (_, Ok(second)) => second.into(),
+        (_, Err(second)) => second,
+    }
+}
+
+/// どちらのスタイルでも解析できなかった入力を、最後に Lazy K のプログラムとして解析する
+///
+/// 空のプログラムは i になってしまうので、空白とコメントだけの入力は元のエラーのままにする
+fn parse_lazy_k_program_or(input: &str, err: anyhow::Error) -> Result<Expr> {
+    if is_blank(input) {
+        return Err(err);
+    }
+    parse_lazy_k_program(input).map_err(|_| err)
+}
+
+/// Lazy K のプログラムとして、空白と # から行末までのコメントしか含まないかどうか
+fn is_blank(input: &str) -> bool {
+    input
+        .lines()
+        .all(|line| line.split('#').next().unwrap_or("").trim().is_empty())
+}
+
+/// どちらのスタイルでも解析できなかったときは、両方のエラーをまとめた ParseError を返す
 pub fn parse_update_or_delete(input: &str) -> Result<Command> {
-    parse_update_or_delete_with_ecmascript_style(input)
-        .or_else(|_err| parse_update_or_delete_with_lazy_k_style(input))
+    parse_update_or_delete_with_ecmascript_style(input).or_else(|ecmascript| {
+        parse_update_or_delete_with_lazy_k_style(input).map_err(|lazy_k| merge(ecmascript, lazy_k))
+    })
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+    use crate::to_string::DisplayStyle;
+
+    #[test]
+    fn test_parse_command_lazy_k_program() {
+        // ECMAScript スタイルでも Lazy K スタイルでも解析できない Lazy K のプログラム
+        assert_eq!(
+            parse_command("S(KI) # comment").unwrap(),
+            Command::Eval(expr::a("s", expr::a("k", "i")))
+        );
+        assert_eq!(
+            parse_command("*ii\n11100").unwrap(),
+            Command::Eval(expr::a(expr::a("ι", "ι"), parse_jot("11100").unwrap()))
+        );
+
+        assert!(parse_command("").is_err());
+        assert!(parse_command("  ").is_err());
+
+        // コメントだけの入力は空のプログラム i として評価しない
+        assert!(parse_command("# c").is_err());
+        assert!(parse_command("  # c\n\n# d").is_err());
+        assert!(parse_expr("# c").is_err());
+    }
+
+    #[test]
+    fn test_parse_command_error() {
+        // どちらのスタイルも 2 行目の先頭で失敗する
+        let err = parse_command("? a\n=").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((err.offset, err.line, err.col), (4, 2, 1));
+        assert!(err.expected.contains(&"end of input".to_string()));
+        assert_eq!(
+            err.style_tried,
+            vec![DisplayStyle::EcmaScript, DisplayStyle::LazyK]
+        );
+
+        // Lazy K スタイルの方が先まで読み進める
+        let err = parse_command("`a").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((err.offset, err.line, err.col), (2, 1, 3));
+        assert_eq!(err.style_tried, vec![DisplayStyle::LazyK]);
+        assert!(!err.expected.is_empty());
+    }
+
+    #[test]
+    fn test_parse_expr_error() {
+        // どちらのスタイルも閉じ括弧の位置で失敗する
+        let err = parse_expr("a)").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((err.offset, err.line, err.col), (1, 1, 2));
+        assert_eq!(err.expected, vec!["end of input"]);
+        assert_eq!(
+            err.style_tried,
+            vec![DisplayStyle::EcmaScript, DisplayStyle::LazyK]
+        );
+
+        let err = parse_update_or_delete("A = a)").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((err.offset, err.line, err.col), (5, 1, 6));
+        assert_eq!(
+            err.style_tried,
+            vec![DisplayStyle::EcmaScript, DisplayStyle::LazyK]
+        );
+    }
 }
@@ -0,0 +1,254 @@
+//! 複数のコマンドを並べたスクリプトを構文解析する
+//!
+//! - `#` または `//` から行末まではコメントとして読み飛ばす
+//! - 空行とコメントだけの行は無視する
+//! - 字下げした行と、開いた括弧が閉じるまでの行は、直前のコマンドの続きとして扱う
+
+use super::{parse_command, ParseError};
+use crate::engine::Command;
+use anyhow::{anyhow, Result};
+
+/// スクリプト中の位置
+///
+/// 行と列はどちらも 1 から数え、列は文字単位で数える
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// スクリプト中でコマンドが占める範囲
+///
+/// end はコマンドの最後の文字の次の位置を指す
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Span {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// スクリプトをコマンドの列に変換する
+///
+/// 構文解析に失敗したコマンドがあれば、スクリプト中の位置に直した ParseError を返す
+/// ParseError 以外のエラーには、コマンドの開始位置を添える
+pub fn parse_script(input: &str) -> Result<Vec<(Command, Span)>> {
+    chunks(input)
+        .into_iter()
+        .map(|chunk| {
+            parse_command(&chunk.source)
+                .map(|command| (command, chunk.span))
+                .map_err(|err| match err.downcast::<ParseError>() {
+                    Ok(err) => chunk.locate(input, err).into(),
+                    Err(err) => anyhow!(
+                        "line {}, column {}: {}",
+                        chunk.span.start.line,
+                        chunk.span.start.column,
+                        err
+                    ),
+                })
+        })
+        .collect()
+}
+
+/// 1 つのコマンドのソース
+struct Chunk {
+    /// 字下げとコメントを取り除いた行を改行でつないだもの
+    source: String,
+
+    span: Span,
+
+    /// source の各行の先頭に対応する、スクリプト中の位置
+    lines: Vec<Position>,
+}
+
+impl Chunk {
+    /// source 中の位置で表された err を、スクリプト中の位置に直す
+    fn locate(&self, input: &str, err: ParseError) -> ParseError {
+        let start = self.lines[err.line - 1];
+        let line = start.line;
+        let col = start.column + err.col - 1;
+        let offset = input
+            .split_inclusive('\n')
+            .take(line - 1)
+            .map(|line| line.chars().count())
+            .sum::<usize>()
+            + col
+            - 1;
+
+        ParseError {
+            offset,
+            line,
+            col,
+            ..err
+        }
+    }
+}
+
+/// スクリプトをコマンドごとのソースに分割する
+fn chunks(input: &str) -> Vec<Chunk> {
+    let mut chunks: Vec<Chunk> = Vec::new();
+    let mut depth = 0;
+
+    for (index, line) in input.lines().enumerate() {
+        let line = strip_comment(line).trim_end();
+        let body = line.trim_start();
+        if body.is_empty() {
+            continue;
+        }
+
+        let start = Position {
+            line: index + 1,
+            column: line.chars().count() - body.chars().count() + 1,
+        };
+        let end = Position {
+            line: index + 1,
+            column: line.chars().count() + 1,
+        };
+
+        let continued = depth > 0 || start.column > 1;
+        match chunks.last_mut() {
+            Some(chunk) if continued => {
+                chunk.source.push('\n');
+                chunk.source.push_str(body);
+                chunk.span.end = end;
+                chunk.lines.push(start);
+            }
+            _ => {
+                depth = 0;
+                chunks.push(Chunk {
+                    source: body.to_string(),
+                    span: Span { start, end },
+                    lines: vec![start],
+                });
+            }
+        }
+
+        for c in body.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => {}
+            }
+        }
+    }
+
+    chunks
+}
+
+/// 行から `#` または `//` 以降のコメントを取り除く
+fn strip_comment(line: &str) -> &str {
+    let end = [line.find('#'), line.find("//")]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(line.len());
+    &line[..end]
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+    use crate::func;
+
+    fn span(start: (usize, usize), end: (usize, usize)) -> Span {
+        Span {
+            start: Position {
+                line: start.0,
+                column: start.1,
+            },
+            end: Position {
+                line: end.0,
+                column: end.1,
+            },
+        }
+    }
+
+    #[test]
+    fn test_parse_script() {
+        let script = "\
+# prelude
+I = x => x // identity
+
+K = (x, y) =>
+  x
+S(x, y, z) = x(z)(
+y(z))
+
+! S(K)(K)(:a)
+";
+        let commands = parse_script(script).unwrap();
+        assert_eq!(
+            commands,
+            vec![
+                (
+                    Command::Update(func::new("I", Vec::<&str>::new(), expr::l("x", "x"))),
+                    span((2, 1), (2, 11))
+                ),
+                (
+                    Command::Update(func::new(
+                        "K",
+                        Vec::<&str>::new(),
+                        expr::l("x", expr::l("y", "x"))
+                    )),
+                    span((4, 1), (5, 4))
+                ),
+                (
+                    Command::Update(func::new(
+                        "S",
+                        vec!["x", "y", "z"],
+                        expr::a(expr::a("x", "z"), expr::a("y", "z"))
+                    )),
+                    span((6, 1), (7, 6))
+                ),
+                (
+                    Command::EvalLast(expr::a(expr::a(expr::a("S", "K"), "K"), ":a")),
+                    span((9, 1), (9, 14))
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_script_lazy_k_style() {
+        let script = "```sxyz = ``xz`yz  # S\n  ``kxy = x\n~~ ^x.x\n";
+        let commands = parse_script(script);
+        assert!(commands.is_err());
+
+        let script = "```sxyz = ``xz`yz  # S\n``kxy = x\n~~ ^x.x\n";
+        let commands: Vec<Command> = parse_script(script)
+            .unwrap()
+            .into_iter()
+            .map(|(command, _)| command)
+            .collect();
+        assert_eq!(
+            commands,
+            vec![
+                Command::Update(func::new(
+                    "s",
+                    vec!["x", "y", "z"],
+                    expr::a(expr::a("x", "z"), expr::a("y", "z"))
+                )),
+                Command::Update(func::new("k", vec!["x", "y"], "x")),
+                Command::Unlambda(2, expr::l("x", "x")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_script_error() {
+        let err = parse_script("I = x => x\n\n  \nK = (x, y) =>\n").unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((err.offset, err.line, err.col), (17, 4, 3));
+
+        // 複数行にわたる定義の 2 行目で失敗した位置を、字下げとコメントを含めたスクリプト中の位置で返す
+        let script = "I = x => x\nS = x => y => # comment\n    z => => x\n";
+        let err = parse_script(script).unwrap_err();
+        let err = err.downcast_ref::<ParseError>().unwrap();
+        assert_eq!((err.offset, err.line, err.col), (41, 3, 7));
+        assert_eq!(script.chars().nth(err.offset), Some('='));
+
+        assert_eq!(parse_script("# comment only\n\n").unwrap(), vec![]);
+    }
+}
@@ -0,0 +1,87 @@
+use crate::expr::{self, Expr};
+use combine::parser::char::{char, space};
+use combine::parser::choice::choice;
+use combine::{any, none_of, parser, skip_many, ParseError, Parser, Stream};
+
+/// Unlambda の式を構文解析する
+///
+/// 評価しても文字を出力しないので、.x は x という名前のシンボルとして、r は改行のシンボルとして扱う
+pub fn expr<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    expr_()
+}
+
+parser! {
+    fn expr_[Input]()(Input) -> Expr
+    where [
+        Input: Stream<Token = char>,
+        Input::Error: ParseError<char, Input::Range, Input::Position>,
+    ]
+    {
+        blank().with(choice((
+            apply(),
+            combinator(),
+            print(),
+        ))).skip(blank())
+    }
+}
+
+fn apply<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    char('`')
+        .with(expr())
+        .and(expr())
+        .map(|(lhs, rhs)| expr::a(lhs, rhs))
+}
+
+fn combinator<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    choice((char('s'), char('k'), char('i'))).map(|c: char| expr::v(c.to_string()))
+}
+
+fn print<Input>() -> impl Parser<Input, Output = Expr>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    choice((
+        char('.').with(any()).map(|c: char| expr::s(c.to_string())),
+        char('r').map(|_| expr::s("\n")),
+    ))
+}
+
+/// 空白と # から行末までのコメント
+fn blank<Input>() -> impl Parser<Input, Output = ()>
+where
+    Input: Stream<Token = char>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    skip_many(choice((
+        space().map(|_| ()),
+        char('#').with(skip_many(none_of("\n".chars()))),
+    )))
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use combine::EasyParser;
+
+    #[test]
+    fn test_expr() {
+        assert_eq!(expr().easy_parse("`ks"), Ok((expr::a("k", "s"), "")));
+        assert_eq!(expr().easy_parse(". "), Ok((expr::s(" "), "")));
+        assert_eq!(expr().easy_parse("r"), Ok((expr::s("\n"), "")));
+    }
+}
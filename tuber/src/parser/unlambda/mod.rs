@@ -0,0 +1,44 @@
+mod expression;
+
+use crate::expr::Expr;
+use anyhow::{anyhow, Result};
+use combine::EasyParser;
+pub use expression::expr;
+
+/// Unlambda のプログラムを式に変換する
+///
+/// s, k, i は同名の変数に、.x と r はシンボルになる
+pub fn parse_expr(s: &str) -> Result<Expr> {
+    let (expr, rest) = expr().easy_parse(s).map_err(|e| anyhow!("{}", e))?;
+
+    if rest.is_empty() {
+        Ok(expr)
+    } else {
+        Err(anyhow!("unexpected token: {}", rest))
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_parse_expr() {
+        assert_eq!(
+            parse_expr("```skk.a").unwrap(),
+            expr::a(expr::a(expr::a("s", "k"), "k"), ":a")
+        );
+
+        // 空白とコメントは読み飛ばす
+        assert_eq!(
+            parse_expr("` # comment\n r i\n").unwrap(),
+            expr::a(expr::s("\n"), "i")
+        );
+
+        assert!(parse_expr("`sk k").is_err());
+        assert!(parse_expr("`sx").is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use super::expression;
+use super::function;
+use crate::engine::Command;
+
+pub fn to_string(command: &Command) -> String {
+    match command {
+        Command::Del(i) => format!("{} = [{}]", i, i),
+        Command::Update(func) => function::to_string(func),
+        Command::Eval(e) => expression::to_string(e),
+        Command::EvalLast(e) => format!("! {}", expression::to_string(e)),
+        Command::EvalHead(len, e) => format!("!{} {}", len, expression::to_string(e)),
+        Command::EvalTail(len, e) => format!("!-{} {}", len, expression::to_string(e)),
+        Command::Query(i) => format!("? {}", i),
+        Command::Context => "?".to_string(),
+        Command::Unlambda(level, e) => {
+            format!(
+                "{} {}",
+                "~".repeat((*level).into()),
+                expression::to_string(e)
+            )
+        }
+        Command::UnlambdaCompare(e) => format!("~* {}", expression::to_string(e)),
+        Command::UnlambdaContext(level) => format!("{}?", "~".repeat((*level).into())),
+        Command::Equiv(lhs, rhs) => format!(
+            "{} == {}",
+            expression::to_string(lhs),
+            expression::to_string(rhs)
+        ),
+        Command::BitLength(e) => format!(":blc {}", expression::to_string(e)),
+        Command::Type(e) => format!(":t {}", expression::to_string(e)),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::command;
+    use crate::expr;
+    use crate::func;
+
+    #[test]
+    fn test_to_string() {
+        let command = command::update(func::new("i", vec!["x"], "x"));
+        assert_eq!(to_string(&command), "i = 0010");
+
+        let command = command::eval_last(expr::a(expr::l("x", "x"), ":a"));
+        assert_eq!(to_string(&command), "! 010010[:a]");
+
+        let command = command::bit_length(expr::l("x", "x"));
+        assert_eq!(to_string(&command), ":blc 0010");
+    }
+}
@@ -0,0 +1,31 @@
+use super::function;
+use crate::context::Context;
+
+pub fn to_string(context: &Context) -> String {
+    let mut funcs = context.iter().map(|(_, func)| func).collect::<Vec<_>>();
+    funcs.sort_by(|l, r| l.name().cmp(r.name()));
+
+    funcs
+        .into_iter()
+        .map(function::to_string)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::func;
+
+    #[test]
+    fn test_to_string() {
+        let context = Context::from(vec![
+            func::new("k", vec!["x", "y"], "x"),
+            func::new("i", vec!["x"], "x"),
+        ]);
+
+        assert_eq!(to_string(&context), "i = 0010\nk = 0000110");
+    }
+}
@@ -0,0 +1,69 @@
+use crate::expr::{Expr, Identifier};
+
+/// 式を Binary Lambda Calculus のビット列で表現する
+///
+/// ラムダ抽象は 00、関数適用は 01、de Bruijn index が n の束縛変数は 1 を n + 1 個並べた後に 0 を置く
+/// BLC では表現できない自由変数とシンボルは [x] や [:a] のように名前を角括弧で囲む
+pub fn to_string(expr: &Expr) -> String {
+    let mut bits = String::new();
+    encode(expr, &mut Vec::new(), &mut bits);
+    bits
+}
+
+fn encode<'a>(expr: &'a Expr, bound_vars: &mut Vec<&'a Identifier>, bits: &mut String) {
+    match expr {
+        Expr::Variable(id) => match bound_vars.iter().rev().position(|param| *param == id) {
+            Some(index) => {
+                bits.push_str(&"1".repeat(index + 1));
+                bits.push('0');
+            }
+            None => bits.push_str(&format!("[{}]", id)),
+        },
+
+        Expr::Symbol(id) => bits.push_str(&format!("[:{}]", id)),
+
+        Expr::Apply { lhs, rhs } => {
+            bits.push_str("01");
+            encode(lhs, bound_vars, bits);
+            encode(rhs, bound_vars, bits);
+        }
+
+        Expr::Lambda { param, body } => {
+            bits.push_str("00");
+            bound_vars.push(param);
+            encode(body, bound_vars, bits);
+            bound_vars.pop();
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_to_string() {
+        // λx.x
+        assert_eq!(to_string(&expr::l("x", "x")), "0010");
+
+        // λx.λy.x
+        assert_eq!(to_string(&expr::l("x", expr::l("y", "x"))), "0000110");
+
+        // λx.λy.λz.``xz`yz
+        let s = expr::l(
+            "x",
+            expr::l(
+                "y",
+                expr::l("z", expr::a(expr::a("x", "z"), expr::a("y", "z"))),
+            ),
+        );
+        assert_eq!(to_string(&s), "00000001011110100111010");
+
+        // 自由変数とシンボル
+        assert_eq!(to_string(&expr::a("i", ":a")), "01[i][:a]");
+        assert_eq!(to_string(&expr::l("x", expr::a("x", "y"))), "000110[y]");
+    }
+}
@@ -0,0 +1,29 @@
+use super::expression;
+use crate::expr::Expr;
+use crate::func::Func;
+
+/// 関数を、引数をラムダ抽象で束縛した式のビット列として表現する
+pub fn to_string(func: &Func) -> String {
+    format!(
+        "{} = {}",
+        func.name(),
+        expression::to_string(&Expr::from(func.clone()))
+    )
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::func;
+
+    #[test]
+    fn test_to_string() {
+        let f = func::new("k", vec!["x", "y"], "x");
+        assert_eq!(to_string(&f), "k = 0000110");
+
+        let f = func::new("TRUE", Vec::<&str>::new(), "k");
+        assert_eq!(to_string(&f), "TRUE = [k]");
+    }
+}
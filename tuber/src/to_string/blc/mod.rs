@@ -0,0 +1,4 @@
+pub mod command;
+pub mod context;
+pub mod expression;
+pub mod function;
@@ -0,0 +1,45 @@
+use crate::calc::Type;
+
+/// TypeScript の型のように、型変数を A, B, C, ... で、関数型を (x: A) => B で表す
+pub fn to_string(t: &Type) -> String {
+    match t {
+        Type::Var(n) => var_name(*n),
+        Type::Arrow(lhs, rhs) => format!("(x: {}) => {}", to_string(lhs), to_string(rhs)),
+    }
+}
+
+/// 0, 1, ..., 25, 26, ... を A, B, ..., Z, A1, ... にする
+fn var_name(n: usize) -> String {
+    let letter = (b'A' + (n % 26) as u8) as char;
+    match n / 26 {
+        0 => letter.to_string(),
+        m => format!("{}{}", letter, m),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(to_string(&Type::Var(0)), "A");
+        assert_eq!(to_string(&Type::Var(27)), "B1");
+        assert_eq!(
+            to_string(&Type::arrow(
+                Type::Var(0),
+                Type::arrow(Type::Var(1), Type::Var(0))
+            )),
+            "(x: A) => (x: B) => A"
+        );
+        assert_eq!(
+            to_string(&Type::arrow(
+                Type::arrow(Type::Var(0), Type::Var(1)),
+                Type::Var(1)
+            )),
+            "(x: (x: A) => B) => B"
+        );
+    }
+}
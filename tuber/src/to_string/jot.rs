@@ -0,0 +1,77 @@
+use crate::error::Error;
+use crate::expr::{self, Expr};
+
+/// s, k, i, ι を関数適用で組み合わせた式を Jot のプログラムにする
+///
+/// k は 11100、s は 11111000、`AB は 1AB で表す
+/// i と ι はそれぞれ ``skk と ``s``si`ks`kk に置き換えてから表す
+pub fn to_string(expr: &Expr) -> Result<String, Error> {
+    match expr {
+        Expr::Variable(id) => match id.as_str() {
+            "s" => Ok("11111000".to_string()),
+            "k" => Ok("11100".to_string()),
+            "i" => to_string(&expr::a(expr::a("s", "k"), "k")),
+            "ι" => to_string(&expr::a(
+                expr::a("s", expr::a(expr::a("s", "i"), expr::a("k", "s"))),
+                expr::a("k", "k"),
+            )),
+            _ => Err(Error::NotEncodable(expr.clone())),
+        },
+
+        Expr::Apply { lhs, rhs } => Ok(format!("1{}{}", to_string(lhs)?, to_string(rhs)?)),
+
+        _ => Err(Error::NotEncodable(expr.clone())),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calc::{equivalence, unlambda_iota, Equivalence};
+    use crate::context::Context;
+    use crate::parser::parse_jot;
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(to_string(&expr::v("k")), Ok("11100".to_string()));
+        assert_eq!(
+            to_string(&expr::a("s", "k")),
+            Ok("11111100011100".to_string())
+        );
+
+        assert_eq!(
+            to_string(&expr::a("x", "k")),
+            Err(Error::NotEncodable(expr::v("x")))
+        );
+        assert_eq!(
+            to_string(&expr::l("x", "x")),
+            Err(Error::NotEncodable(expr::l("x", "x")))
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let context = Context::default();
+
+        for e in [
+            expr::v("i"),
+            expr::v("ι"),
+            expr::a("ι", "ι"),
+            expr::a(expr::a("s", "k"), "k"),
+            expr::a("k", expr::a("s", "i")),
+            // unlambda_iota の出力も Jot を経由して元の式に戻る
+            unlambda_iota(&context, expr::v("k")),
+        ] {
+            let jot = to_string(&e).unwrap();
+            assert_eq!(
+                equivalence(&context, parse_jot(&jot).unwrap(), e.clone(), 1000),
+                Ok(Equivalence::Equal),
+                "{} => {}",
+                e,
+                jot
+            );
+        }
+    }
+}
@@ -19,6 +19,15 @@ pub fn to_string(command: &Command) -> String {
                 expression::to_string(e)
             )
         }
+        Command::UnlambdaCompare(e) => format!("~* {}", expression::to_string(e)),
+        Command::UnlambdaContext(level) => format!("{}?", "~".repeat((*level).into())),
+        Command::Equiv(lhs, rhs) => format!(
+            "{} == {}",
+            expression::to_string(lhs),
+            expression::to_string(rhs)
+        ),
+        Command::BitLength(e) => format!(":blc {}", expression::to_string(e)),
+        Command::Type(e) => format!(":t {}", expression::to_string(e)),
     }
 }
 
@@ -139,4 +148,34 @@ mod tests {
         let command = command::unlambda(4, expr::l("x", "y"));
         assert_eq!(to_string(&command), "~~~~ λx.y");
     }
+
+    #[test]
+    fn test_unlambda_compare() {
+        let command = command::unlambda_compare(expr::l("x", "y"));
+        assert_eq!(to_string(&command), "~* λx.y");
+    }
+
+    #[test]
+    fn test_unlambda_context() {
+        let command = command::unlambda_context(2);
+        assert_eq!(to_string(&command), "~~?");
+    }
+
+    #[test]
+    fn test_equiv() {
+        let command = command::equiv(expr::l("x", "x"), "i");
+        assert_eq!(to_string(&command), "λx.x == i");
+    }
+
+    #[test]
+    fn test_bit_length() {
+        let command = command::bit_length(expr::l("x", "x"));
+        assert_eq!(to_string(&command), ":blc λx.x");
+    }
+
+    #[test]
+    fn test_type() {
+        let command = command::type_of(expr::l("x", "x"));
+        assert_eq!(to_string(&command), ":t λx.x");
+    }
 }
@@ -2,3 +2,4 @@ pub mod command;
 pub mod context;
 pub mod expression;
 pub mod function;
+pub mod types;
@@ -0,0 +1,48 @@
+use crate::calc::Type;
+
+/// 型変数を a, b, c, ... で、関数型を -> で表す
+pub fn to_string(t: &Type) -> String {
+    match t {
+        Type::Var(n) => var_name(*n),
+        Type::Arrow(lhs, rhs) => match lhs.as_ref() {
+            Type::Arrow(..) => format!("({}) -> {}", to_string(lhs), to_string(rhs)),
+            Type::Var(_) => format!("{} -> {}", to_string(lhs), to_string(rhs)),
+        },
+    }
+}
+
+/// 0, 1, ..., 25, 26, ... を a, b, ..., z, a1, ... にする
+fn var_name(n: usize) -> String {
+    let letter = (b'a' + (n % 26) as u8) as char;
+    match n / 26 {
+        0 => letter.to_string(),
+        m => format!("{}{}", letter, m),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_string() {
+        assert_eq!(to_string(&Type::Var(0)), "a");
+        assert_eq!(to_string(&Type::Var(27)), "b1");
+        assert_eq!(
+            to_string(&Type::arrow(
+                Type::Var(0),
+                Type::arrow(Type::Var(1), Type::Var(0))
+            )),
+            "a -> b -> a"
+        );
+        assert_eq!(
+            to_string(&Type::arrow(
+                Type::arrow(Type::Var(0), Type::Var(1)),
+                Type::Var(1)
+            )),
+            "(a -> b) -> b"
+        );
+    }
+}
@@ -1,12 +1,19 @@
+mod blc;
 mod ecmascript;
+mod jot;
 mod lazy_k;
+mod unlambda;
 
+use crate::calc::{FoldBack, Type};
 use crate::context::Context;
 use crate::engine::Command;
 use crate::expr::Expr;
 use crate::func::Func;
 use std::fmt::Display;
 
+pub use jot::to_string as to_jot;
+pub use unlambda::to_string as to_unlambda;
+
 impl Display for Command {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}", lazy_k::command::to_string(self))
@@ -31,16 +38,37 @@ impl Display for Func {
     }
 }
 
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", lazy_k::types::to_string(self))
+    }
+}
+
 // ========================================================================== //
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum DisplayStyle {
     EcmaScript,
     LazyK,
+    Blc,
+}
+
+/// 整形する前に式に施す後処理
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FormatOptions<'a> {
+    /// Context に定義済みの関数と同じ部分式を関数の名前に置き換える
+    pub fold_back: Option<&'a FoldBack>,
 }
 
 pub trait Format {
     fn format(&self, style: &DisplayStyle) -> String;
+
+    /// 後処理を施してから整形する
+    ///
+    /// 後処理の対象にならない値はそのまま整形する
+    fn format_with(&self, style: &DisplayStyle, _options: &FormatOptions) -> String {
+        self.format(style)
+    }
 }
 
 impl Format for Command {
@@ -48,6 +76,7 @@ impl Format for Command {
         match style {
             DisplayStyle::EcmaScript => ecmascript::command::to_string(self),
             DisplayStyle::LazyK => lazy_k::command::to_string(self),
+            DisplayStyle::Blc => blc::command::to_string(self),
         }
     }
 }
@@ -56,6 +85,7 @@ impl Format for &Command {
         match style {
             DisplayStyle::EcmaScript => ecmascript::command::to_string(self),
             DisplayStyle::LazyK => lazy_k::command::to_string(self),
+            DisplayStyle::Blc => blc::command::to_string(self),
         }
     }
 }
@@ -65,6 +95,7 @@ impl Format for Context {
         match style {
             DisplayStyle::EcmaScript => ecmascript::context::to_string(self),
             DisplayStyle::LazyK => lazy_k::context::to_string(self),
+            DisplayStyle::Blc => blc::context::to_string(self),
         }
     }
 }
@@ -73,6 +104,7 @@ impl Format for &Context {
         match style {
             DisplayStyle::EcmaScript => ecmascript::context::to_string(self),
             DisplayStyle::LazyK => lazy_k::context::to_string(self),
+            DisplayStyle::Blc => blc::context::to_string(self),
         }
     }
 }
@@ -82,6 +114,14 @@ impl Format for Expr {
         match style {
             DisplayStyle::EcmaScript => ecmascript::expression::to_string(self),
             DisplayStyle::LazyK => lazy_k::expression::to_string(self),
+            DisplayStyle::Blc => blc::expression::to_string(self),
+        }
+    }
+
+    fn format_with(&self, style: &DisplayStyle, options: &FormatOptions) -> String {
+        match options.fold_back {
+            Some(fold_back) => fold_back.fold(self).format(style),
+            None => self.format(style),
         }
     }
 }
@@ -90,8 +130,13 @@ impl Format for &Expr {
         match style {
             DisplayStyle::EcmaScript => ecmascript::expression::to_string(self),
             DisplayStyle::LazyK => lazy_k::expression::to_string(self),
+            DisplayStyle::Blc => blc::expression::to_string(self),
         }
     }
+
+    fn format_with(&self, style: &DisplayStyle, options: &FormatOptions) -> String {
+        (*self).format_with(style, options)
+    }
 }
 
 impl Format for Func {
@@ -99,6 +144,7 @@ impl Format for Func {
         match style {
             DisplayStyle::EcmaScript => ecmascript::function::to_string(self),
             DisplayStyle::LazyK => lazy_k::function::to_string(self),
+            DisplayStyle::Blc => blc::function::to_string(self),
         }
     }
 }
@@ -107,6 +153,44 @@ impl Format for &Func {
         match style {
             DisplayStyle::EcmaScript => ecmascript::function::to_string(self),
             DisplayStyle::LazyK => lazy_k::function::to_string(self),
+            DisplayStyle::Blc => blc::function::to_string(self),
         }
     }
 }
+
+impl Format for Type {
+    fn format(&self, style: &DisplayStyle) -> String {
+        match style {
+            DisplayStyle::EcmaScript => ecmascript::types::to_string(self),
+            // BLC には型の表記がないので Lazy K の表記を使う
+            DisplayStyle::LazyK | DisplayStyle::Blc => lazy_k::types::to_string(self),
+        }
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+
+    #[test]
+    fn test_format_with_fold_back() {
+        let fold_back = FoldBack::new(&Context::default());
+        let options = FormatOptions {
+            fold_back: Some(&fold_back),
+        };
+
+        let e = expr::a(":a", expr::l("x", expr::l("y", "x")));
+        assert_eq!(e.format(&DisplayStyle::EcmaScript), ":a((x, y) => x)");
+        assert_eq!(
+            e.format_with(&DisplayStyle::EcmaScript, &options),
+            ":a(TRUE)"
+        );
+        assert_eq!(
+            e.format_with(&DisplayStyle::EcmaScript, &FormatOptions::default()),
+            ":a((x, y) => x)"
+        );
+    }
+}
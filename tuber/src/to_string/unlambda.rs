@@ -0,0 +1,63 @@
+use crate::error::Error;
+use crate::expr::Expr;
+
+/// s, k, i, ι とシンボルを関数適用で組み合わせた式を Unlambda のプログラムにする
+///
+/// 1文字のシンボルは .x で、改行のシンボルは r で表す
+/// ι は ``s``si`ks`kk に置き換えてから表す
+pub fn to_string(expr: &Expr) -> Result<String, Error> {
+    match expr {
+        Expr::Variable(id) => match id.as_str() {
+            "s" | "k" | "i" => Ok(id.to_string()),
+            "ι" => Ok("``s``si`ks`kk".to_string()),
+            _ => Err(Error::NotEncodable(expr.clone())),
+        },
+
+        Expr::Symbol(id) => {
+            let mut chars = id.as_str().chars();
+            match (chars.next(), chars.next()) {
+                (Some('\n'), None) => Ok("r".to_string()),
+                (Some(c), None) => Ok(format!(".{}", c)),
+                _ => Err(Error::NotEncodable(expr.clone())),
+            }
+        }
+
+        Expr::Apply { lhs, rhs } => Ok(format!("`{}{}", to_string(lhs)?, to_string(rhs)?)),
+
+        Expr::Lambda { .. } => Err(Error::NotEncodable(expr.clone())),
+    }
+}
+
+// ========================================================================== //
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expr;
+    use crate::parser::parse_unlambda;
+
+    #[test]
+    fn test_to_string() {
+        let e = expr::a(expr::a(expr::a("s", "k"), "k"), ":a");
+        assert_eq!(to_string(&e), Ok("```skk.a".to_string()));
+        assert_eq!(parse_unlambda("```skk.a").unwrap(), e);
+
+        let e = expr::a(expr::s("\n"), "i");
+        assert_eq!(to_string(&e), Ok("`ri".to_string()));
+        assert_eq!(parse_unlambda("`ri").unwrap(), e);
+
+        assert_eq!(
+            to_string(&expr::a("ι", "i")),
+            Ok("```s``si`ks`kki".to_string())
+        );
+
+        assert_eq!(
+            to_string(&expr::s("ab")),
+            Err(Error::NotEncodable(expr::s("ab")))
+        );
+        assert_eq!(
+            to_string(&expr::a("x", "i")),
+            Err(Error::NotEncodable(expr::v("x")))
+        );
+    }
+}